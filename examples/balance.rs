@@ -0,0 +1,321 @@
+/// Play lots of seeded games with the AI to see how a balance change plays out
+///
+/// cargo run --release --example balance --target x86_64-unknown-linux-gnu -- \
+///     --games 1000 --reaction 2 --aim-error 3 --reload-at 0 --format json
+///
+/// CSV has a row per game, JSON the summary and every game. With `--adaptive`
+/// JSON also logs every change the difficulty director made.
+use game_dont_panic::ai::{Player, Skill};
+use game_dont_panic::director::{self, Change};
+use game_dont_panic::game::{Game, Screen};
+use game_dont_panic::modes::GameMode;
+use game_dont_panic::opps::{self, Cause};
+use game_dont_panic::rng::XorShift;
+use rand_core::RngCore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::{env, process};
+
+#[derive(Serialize)]
+struct Config {
+    games: u32,
+    seed: u32,
+    mode: &'static str,
+    max_ticks: u32,
+    reaction: u8,
+    aim_error: u8,
+    reload_at: u8,
+    reload_idle: bool,
+    adaptive: bool,
+}
+
+#[derive(Serialize)]
+struct Run {
+    seed: u32,
+    score: u32,
+    ticks: u32,
+    kills: u32,
+    shots: u32,
+    hits: u32,
+    /// What took the last life, or why else the game ended
+    end: &'static str,
+    tier_ticks: Vec<u32>,
+    /// Difficulty level the director ended up at, 0 without one
+    level: i8,
+    adjustments: Vec<Adjustment>,
+}
+
+/// A change of the director, logged as it happened
+#[derive(Serialize)]
+struct Adjustment {
+    tick: u32,
+    level: i8,
+    accuracy: Option<u32>,
+    closest: u8,
+    since_reload: u32,
+    hurt: bool,
+    spawn_rate: i8,
+    speed: i8,
+    health: i8,
+    gunners: i8,
+}
+
+impl From<&Change> for Adjustment {
+    fn from(change: &Change) -> Self {
+        Self {
+            tick: change.tick,
+            level: change.level,
+            accuracy: change.accuracy,
+            closest: change.closest,
+            since_reload: change.since_reload,
+            hurt: change.hurt,
+            spawn_rate: change.adjustment.spawn_rate,
+            speed: change.adjustment.speed,
+            health: change.adjustment.health,
+            gunners: change.adjustment.gunners,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Summary {
+    scores: Distribution,
+    ticks: Distribution,
+    ends: BTreeMap<&'static str, u32>,
+    /// Average ticks spent per difficulty tier
+    tier_ticks: Vec<f64>,
+    /// Games that made it to each tier
+    tier_reached: Vec<u32>,
+    /// Games that ended at each difficulty level, from -MAX_LEVEL up
+    levels: Vec<u32>,
+    adjustments: Distribution,
+}
+
+#[derive(Serialize)]
+struct Distribution {
+    min: u32,
+    p10: u32,
+    p25: u32,
+    median: u32,
+    p75: u32,
+    p90: u32,
+    max: u32,
+    mean: f64,
+}
+
+impl Distribution {
+    fn new(mut values: Vec<u32>) -> Self {
+        values.sort_unstable();
+        let at = |percent: usize| values[(values.len() - 1) * percent / 100];
+        Self {
+            min: at(0),
+            p10: at(10),
+            p25: at(25),
+            median: at(50),
+            p75: at(75),
+            p90: at(90),
+            max: at(100),
+            mean: values.iter().map(|&value| value as f64).sum::<f64>() / values.len() as f64,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report {
+    config: Config,
+    summary: Summary,
+    runs: Vec<Run>,
+}
+
+fn main() {
+    let mut config = Config {
+        games: 1000,
+        seed: 1,
+        mode: GameMode::Endless.name(),
+        max_ticks: 20_000,
+        reaction: 0,
+        aim_error: 0,
+        reload_at: Skill::default().reload_at,
+        reload_idle: true,
+        adaptive: false,
+    };
+    let mut json = false;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--no-reload-idle" {
+            config.reload_idle = false;
+            continue;
+        }
+        if arg == "--adaptive" {
+            config.adaptive = true;
+            continue;
+        }
+        let Some(value) = args.next() else {
+            usage(&format!("{arg} needs a value"));
+        };
+        let number = || {
+            value
+                .parse()
+                .unwrap_or_else(|_| usage(&format!("{arg} {value}")))
+        };
+        match arg.as_str() {
+            "--games" => config.games = number(),
+            "--seed" => config.seed = number(),
+            "--max-ticks" => config.max_ticks = number(),
+            "--reaction" => config.reaction = number() as u8,
+            "--aim-error" => config.aim_error = number() as u8,
+            "--reload-at" => config.reload_at = number() as u8,
+            "--mode" => {
+                let name = value.replace('-', " ");
+                // challenges are time attack on a seeded lawn, the bot seeds its own games
+                let mode = GameMode::RANKED
+                    .iter()
+                    .find(|mode| mode.name().eq_ignore_ascii_case(&name))
+                    .unwrap_or_else(|| usage(&format!("unknown mode {value}")));
+                config.mode = mode.name();
+            }
+            "--format" => match value.as_str() {
+                "csv" => json = false,
+                "json" => json = true,
+                _ => usage(&format!("unknown format {value}")),
+            },
+            _ => usage(&format!("unknown option {arg}")),
+        }
+    }
+    if config.games == 0 {
+        usage("no games to play");
+    }
+
+    let mut seeds = XorShift::new(config.seed);
+    let runs: Vec<Run> = (0..config.games)
+        .map(|_| play(&config, seeds.next_u32()))
+        .collect();
+
+    if json {
+        let report = Report {
+            summary: summarize(&runs),
+            config,
+            runs,
+        };
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        let tiers: Vec<String> = (0..opps::TIER_COUNT)
+            .map(|tier| format!("tier{tier}_ticks"))
+            .collect();
+        println!(
+            "seed,score,ticks,kills,shots,hits,end,{},level,adjustments",
+            tiers.join(",")
+        );
+        for run in &runs {
+            let tiers: Vec<String> = run.tier_ticks.iter().map(u32::to_string).collect();
+            println!(
+                "{},{},{},{},{},{},{},{},{},{}",
+                run.seed,
+                run.score,
+                run.ticks,
+                run.kills,
+                run.shots,
+                run.hits,
+                run.end,
+                tiers.join(","),
+                run.level,
+                run.adjustments.len()
+            );
+        }
+    }
+}
+
+fn usage(error: &str) -> ! {
+    eprintln!("{error}");
+    eprintln!(
+        "usage: balance [--games N] [--seed N] [--mode NAME] [--max-ticks N] [--reaction TICKS] \
+         [--aim-error PIXELS] [--reload-at ROUNDS] [--no-reload-idle] [--adaptive] [--format csv|json]"
+    );
+    process::exit(2);
+}
+
+fn play(config: &Config, seed: u32) -> Run {
+    let mode = GameMode::RANKED
+        .into_iter()
+        .find(|mode| mode.name() == config.mode)
+        .unwrap();
+    let skill = Skill {
+        reaction: config.reaction,
+        aim_error: config.aim_error,
+        reload_at: config.reload_at,
+        reload_idle: config.reload_idle,
+    };
+    let mut game = Game::new().with_mode(mode).with_adaptive(config.adaptive);
+    let mut player = Player::new().with_skill(skill, seed.rotate_left(16));
+    let mut random = XorShift::new(seed);
+
+    let mut adjustments = Vec::new();
+    while game.screen() != Screen::Wasted && game.stats().ticks < config.max_ticks {
+        game.tick(&mut random);
+        if let Some(director) = game.director() {
+            if director.changes() as usize > adjustments.len() {
+                adjustments.extend(director.last_change().map(Adjustment::from));
+            }
+        }
+        if let Some(action) = player.act(&game) {
+            game.action(&action);
+        }
+    }
+
+    let stats = game.stats();
+    let end = match stats.death {
+        Some(cause) => cause.name(),
+        None if game.screen() != Screen::Wasted => "max ticks",
+        None if mode.target_score().is_some() => "target",
+        None => "time",
+    };
+    Run {
+        seed,
+        score: game.score(),
+        ticks: stats.ticks,
+        kills: stats.kills,
+        shots: stats.shots,
+        hits: stats.hits,
+        end,
+        tier_ticks: (0..opps::TIER_COUNT)
+            .map(|tier| stats.ticks_in(tier))
+            .collect(),
+        level: game.director().map_or(0, |director| director.level()),
+        adjustments,
+    }
+}
+
+fn summarize(runs: &[Run]) -> Summary {
+    let mut ends = BTreeMap::new();
+    for run in runs {
+        *ends.entry(run.end).or_insert(0) += 1;
+    }
+    // every cause shows up, also the ones that never took a life
+    for cause in Cause::ALL {
+        ends.entry(cause.name()).or_insert(0);
+    }
+
+    Summary {
+        scores: Distribution::new(runs.iter().map(|run| run.score).collect()),
+        ticks: Distribution::new(runs.iter().map(|run| run.ticks).collect()),
+        ends,
+        tier_ticks: (0..opps::TIER_COUNT)
+            .map(|tier| {
+                let total: u32 = runs.iter().map(|run| run.tier_ticks[tier]).sum();
+                total as f64 / runs.len() as f64
+            })
+            .collect(),
+        tier_reached: (0..opps::TIER_COUNT)
+            .map(|tier| runs.iter().filter(|run| run.tier_ticks[tier] > 0).count() as u32)
+            .collect(),
+        levels: (-director::MAX_LEVEL..=director::MAX_LEVEL)
+            .map(|level| runs.iter().filter(|run| run.level == level).count() as u32)
+            .collect(),
+        adjustments: Distribution::new(
+            runs.iter()
+                .map(|run| run.adjustments.len() as u32)
+                .collect(),
+        ),
+    }
+}
@@ -4,7 +4,7 @@ use embedded_graphics::{
     mono_font::{ascii, MonoTextStyle, MonoTextStyleBuilder},
     pixelcolor::BinaryColor,
     prelude::*,
-    primitives::{Circle, PrimitiveStyle, Triangle},
+    primitives::{Circle, Line, PrimitiveStyle, Triangle},
 };
 use embedded_graphics_simulator::{
     BinaryColorTheme, OutputSettingsBuilder, SimulatorDisplay, Window,
@@ -38,6 +38,7 @@ pub enum Chamber {
     Empty,
     Loaded,
     Shot,
+    Dud,
 }
 
 #[derive(serde::Deserialize)]
@@ -85,6 +86,17 @@ fn main() -> Result<(), core::convert::Infallible> {
                     .into_styled(BLACK_FILL)
                     .draw(&mut display)?;
             }
+            Chamber::Dud => {
+                Circle::new(Point::new(x, y), 16)
+                    .into_styled(WHITE_FILL)
+                    .draw(&mut display)?;
+                Line::new(Point::new(x + 4, y + 4), Point::new(x + 11, y + 11))
+                    .into_styled(BLACK_LINE)
+                    .draw(&mut display)?;
+                Line::new(Point::new(x + 11, y + 4), Point::new(x + 4, y + 11))
+                    .into_styled(BLACK_LINE)
+                    .draw(&mut display)?;
+            }
         }
     }
 
@@ -0,0 +1,35 @@
+/// Check the verification code from the game over screen of a challenge
+///
+/// cargo run --example verify-challenge --target x86_64-unknown-linux-gnu -- \
+///     daily 2026-10-18 <score> <ticks> <code>
+///
+/// The ticks are on the second page of the game over screen.
+use game_dont_panic::challenge::{self, Challenge};
+use std::{env, process};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [period, date, score, ticks, code] = args.as_slice() else {
+        eprintln!("usage: verify-challenge <daily|weekly> <YYYY-MM-DD> <score> <ticks> <code>");
+        process::exit(2);
+    };
+
+    let Some(challenge) = Challenge::parse(format!("{period} {date}").as_bytes()) else {
+        eprintln!("not a challenge: {period} {date}");
+        process::exit(2);
+    };
+    let (Ok(score), Ok(ticks)) = (score.parse(), ticks.parse()) else {
+        eprintln!("score and ticks have to be numbers");
+        process::exit(2);
+    };
+
+    let expected =
+        challenge::format_code(challenge::verification_code(challenge.seed(), score, ticks));
+    let expected = String::from_utf8_lossy(&expected);
+    println!("seed {:08x}, expected code {expected}", challenge.seed());
+    if !code.eq_ignore_ascii_case(&expected) {
+        println!("code {code} does not match");
+        process::exit(1);
+    }
+    println!("code matches");
+}
@@ -0,0 +1,182 @@
+use crate::boss;
+use crate::game::{self, Action, Button, Direction, Game, Screen};
+use crate::gfx;
+use crate::guns::{Chamber, Revolver, Weapon};
+use crate::rng::XorShift;
+use rand_core::RngCore;
+
+// close enough to the target to pull the trigger
+const FIRE_TOLERANCE: u8 = 4;
+// automatic weapons fire in short bursts
+const BURST_TICKS: u8 = 4;
+
+/// How well the AI plays
+#[derive(Clone, Copy)]
+pub struct Skill {
+    /// Ticks without any input after every input
+    pub reaction: u8,
+    /// The most pixels the aim is off, in either direction, rolled again after every shot
+    pub aim_error: u8,
+    /// The revolver is reloaded once it has this few rounds left
+    pub reload_at: u8,
+    /// Also top up the revolver when there is nothing to shoot at
+    pub reload_idle: bool,
+}
+
+impl Default for Skill {
+    fn default() -> Self {
+        Self {
+            reaction: 0,
+            aim_error: 0,
+            reload_at: 1,
+            reload_idle: true,
+        }
+    }
+}
+
+/// Plays the game through the same inputs as a person, one per tick
+///
+/// It aims at the nearest opponent, reloads when the revolver runs low and
+/// prefers any primary weapon it picked up. Without opponents it goes for pickups.
+pub struct Player {
+    skill: Skill,
+    random: XorShift,
+    // the button that is held down, and for how many more ticks
+    held: Option<(Button, u8)>,
+    // ticks until the next input
+    wait: u8,
+    aim_error: i16,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player {
+    pub fn new() -> Self {
+        Self {
+            skill: Skill::default(),
+            random: XorShift::new(0),
+            held: None,
+            wait: 0,
+            aim_error: 0,
+        }
+    }
+
+    /// Play less than perfectly, the aim error is rolled from `seed`
+    pub fn with_skill(mut self, skill: Skill, seed: u32) -> Self {
+        self.skill = skill;
+        self.random = XorShift::new(seed);
+        self
+    }
+
+    /// The input for this tick, if any
+    pub fn act(&mut self, game: &Game) -> Option<Action> {
+        if let Some((button, ticks)) = &mut self.held {
+            if *ticks > 0 {
+                *ticks -= 1;
+                return None;
+            }
+            let button = *button;
+            self.held = None;
+            return Some(Action::Release(button));
+        }
+
+        if self.wait > 0 {
+            self.wait -= 1;
+            return None;
+        }
+        let action = match game.screen() {
+            Screen::Start => self.press(Button::Shoot, 0),
+            Screen::Normal => self.fight(game),
+            Screen::Reload => self.reload(&game.secondary_gun),
+            _ => None,
+        };
+        if action.is_some() {
+            self.wait = self.skill.reaction;
+        }
+        action
+    }
+
+    fn press(&mut self, button: Button, ticks: u8) -> Option<Action> {
+        self.held = Some((button, ticks));
+        Some(Action::Press(button))
+    }
+
+    fn fight(&mut self, game: &Game) -> Option<Action> {
+        let gun = game.gun();
+        let revolver = &game.secondary_gun;
+        let target = target(game);
+
+        // the revolver is the backup, switch to the primary weapon whenever there is one
+        if gun.is_reloadable() && game.holstered_gun().is_some() {
+            return self.press(Button::ReloadToggle, game::SWITCH_HOLD_TICKS);
+        }
+        // reload when low, or when there is nothing to shoot at
+        let idle = self.skill.reload_idle && target.is_none() && gun.ammo() < gun.capacity();
+        let low = gun.ammo() <= self.skill.reload_at || idle;
+        if gun.is_reloadable() && low && revolver.reserve() > 0 {
+            return self.press(Button::ReloadToggle, 0);
+        }
+
+        // line up the barrel with the target, or ferris with the next pickup
+        let (y, shoot) = match target {
+            Some(y) => {
+                let y = (y as i16 + self.aim_error).clamp(0, u8::MAX as i16) as u8;
+                (y.saturating_sub(gun.offset()), gun.ammo() > 0)
+            }
+            None => {
+                let pickup = game.pickups().iter().min_by_key(|pickup| pickup.x())?;
+                (pickup.y().saturating_sub(gfx::FERRIS_HEIGHT / 2), false)
+            }
+        };
+        // a bullet that is still on its way might already be enough, don't waste another one
+        let in_flight =
+            gun.fire_rate().is_none() && game.bullets().any(|bullet| !bullet.has_struck());
+        if shoot && !in_flight && game.y().abs_diff(y) <= FIRE_TOLERANCE {
+            let hold = if gun.fire_rate().is_some() {
+                BURST_TICKS
+            } else {
+                0
+            };
+            let error = self.skill.aim_error as u32;
+            self.aim_error = (self.random.next_u32() % (2 * error + 1)) as i16 - error as i16;
+            return self.press(Button::Shoot, hold);
+        }
+        match game.y().abs_diff(y) {
+            distance if distance < game::STEP_Y => None,
+            _ if game.y() < y => Some(Action::Rotate(Direction::Clockwise)),
+            _ => Some(Action::Rotate(Direction::CounterClock)),
+        }
+    }
+
+    // load every chamber that can be loaded, then go back to the lawn
+    fn reload(&mut self, revolver: &Revolver) -> Option<Action> {
+        if revolver.ammo() == revolver.capacity() || revolver.reserve() == 0 {
+            return self.press(Button::ReloadToggle, 0);
+        }
+        match revolver.chambers().next() {
+            Some(Chamber::Empty | Chamber::Shot | Chamber::Dud) => self.press(Button::Shoot, 0),
+            _ => Some(Action::Rotate(Direction::Clockwise)),
+        }
+    }
+}
+
+/// The row to shoot at, in the middle of the closest opponent or boss part
+fn target(game: &Game) -> Option<u8> {
+    let opponents = game
+        .lawn
+        .opponents()
+        .map(|opp| (opp.distance(), opp.y() + gfx::OPPONENT_HEIGHT / 2));
+    let boss = game.lawn.boss().and_then(|boss| {
+        let part = boss.parts().iter().position(|&health| health > 0)? as u8;
+        let y = boss.y() + part * boss::BOSS_PART_HEIGHT + boss::BOSS_PART_HEIGHT / 2;
+        Some((boss.distance(), y))
+    });
+    opponents
+        .chain(boss)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, y)| y)
+}
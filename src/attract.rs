@@ -0,0 +1,82 @@
+use crate::ai::Player;
+use crate::game::{Game, Screen};
+use rand_core::RngCore;
+
+// ten seconds on the start screen without any input until the attract mode starts
+const IDLE_TICKS: u16 = 100;
+// a demo game that goes on for more than a minute is cut short
+const DEMO_TICKS: u16 = 600;
+// the best scores are shown for five seconds between demo games
+const SCORES_TICKS: u16 = 50;
+
+enum Show {
+    /// Ticks the demo game has been going
+    Demo(u16),
+    Scores(u16),
+}
+
+/// Demo games played by the AI and the best scores, taking turns while nobody plays
+#[derive(Default)]
+pub struct Attract {
+    idle: u16,
+    show: Option<Show>,
+    // only one game at a time, the same one is restarted for every demo
+    game: Game,
+    player: Player,
+}
+
+impl Attract {
+    /// Only counts as idle while `screen` is the start screen
+    pub fn tick<R: RngCore>(&mut self, screen: Screen, random: R) {
+        if screen != Screen::Start {
+            self.wake();
+            return;
+        }
+
+        match &mut self.show {
+            None => {
+                self.idle = self.idle.saturating_add(1);
+                if self.idle >= IDLE_TICKS {
+                    self.start_demo();
+                }
+            }
+            Some(Show::Demo(ticks)) => {
+                self.game.tick(random);
+                if let Some(action) = self.player.act(&self.game) {
+                    self.game.action(&action);
+                }
+                *ticks += 1;
+                if self.game.screen() == Screen::Wasted || *ticks >= DEMO_TICKS {
+                    self.show = Some(Show::Scores(0));
+                }
+            }
+            Some(Show::Scores(ticks)) => {
+                *ticks += 1;
+                if *ticks >= SCORES_TICKS {
+                    self.start_demo();
+                }
+            }
+        }
+    }
+
+    fn start_demo(&mut self) {
+        self.show = Some(Show::Demo(0));
+        self.game = Game::new();
+        self.player = Player::new();
+    }
+
+    /// Any input ends the attract mode, returns true if it was showing
+    pub fn wake(&mut self) -> bool {
+        self.idle = 0;
+        self.show.take().is_some()
+    }
+
+    /// The demo game that is being played
+    pub fn demo(&self) -> Option<&Game> {
+        matches!(self.show, Some(Show::Demo(_))).then_some(&self.game)
+    }
+
+    pub fn is_showing_scores(&self) -> bool {
+        matches!(self.show, Some(Show::Scores(_)))
+    }
+}
@@ -0,0 +1,401 @@
+use crate::gfx;
+use crate::opps;
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Circle, Rectangle, RoundedRectangle},
+};
+
+// a boss shows up every time the score passes one of these milestones
+pub const BOSS_INTERVAL: u32 = 50;
+pub const BOSS_BONUS: u32 = 10;
+
+pub const BOSS_PARTS: usize = 3;
+pub const BOSS_PART_HEALTH: u8 = 4;
+pub const BOSS_WIDTH: u8 = 28;
+pub const BOSS_PART_HEIGHT: u8 = 13;
+// the lowest the topmost part may go, so every part stays reachable
+pub const BOSS_MAX_Y: u8 = opps::MAX_SPAWN_Y - (BOSS_PARTS as u8 - 1) * BOSS_PART_HEIGHT;
+
+// where the boss stops walking in and starts its attack pattern
+const HOLD_X: u8 = 24;
+// how much closer the boss holds after every charge
+const HOLD_ADVANCE: u8 = 3;
+const CHARGE_DISTANCE: u8 = 12;
+const CHARGE_INTERVAL: u8 = 30;
+const ENRAGED_CHARGE_INTERVAL: u8 = 15;
+
+const HEALTH_BAR_X: i32 = gfx::HUD_CENTER_X;
+const HEALTH_BAR_WIDTH: u32 = 14;
+const HEALTH_BAR_HEIGHT: u32 = 4;
+
+static_assertions::const_assert!(
+    opps::MAX_SPAWN_Y - opps::MIN_SPAWN_Y >= (BOSS_PARTS as u8 - 1) * BOSS_PART_HEIGHT
+);
+static_assertions::const_assert!(HOLD_X < opps::SPAWN_OFFSET_X);
+// the health bar is part of the HUD strip
+static_assertions::const_assert!(HEALTH_BAR_HEIGHT <= gfx::HUD_HEIGHT as u32);
+static_assertions::const_assert!(CHARGE_DISTANCE < HOLD_X);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// Walking in from the right edge of the screen
+    Entering,
+    /// Sweeping up and down, waiting for the next charge
+    Sweep,
+    /// Lunging towards ferris
+    Charge,
+    /// Backing off after a charge
+    Retreat,
+}
+
+pub struct Boss {
+    x: u8,
+    y: u8,
+    descending: bool,
+    hold_x: u8,
+    phase: Phase,
+    next_step: u8,
+    next_charge: u8,
+    parts: [u8; BOSS_PARTS],
+}
+
+impl Default for Boss {
+    fn default() -> Self {
+        Self {
+            x: opps::SPAWN_OFFSET_X,
+            y: opps::MIN_SPAWN_Y,
+            descending: true,
+            hold_x: HOLD_X,
+            phase: Phase::Entering,
+            next_step: 0,
+            next_charge: CHARGE_INTERVAL,
+            parts: [BOSS_PART_HEALTH; BOSS_PARTS],
+        }
+    }
+}
+
+impl Boss {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn x(&self) -> u8 {
+        self.x + gfx::FERRIS_OFFSET
+    }
+
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    /// Pixels left until the boss reaches ferris
+    pub fn distance(&self) -> u8 {
+        self.x
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// The remaining health of every part, from top to bottom
+    pub fn parts(&self) -> &[u8; BOSS_PARTS] {
+        &self.parts
+    }
+
+    pub fn health(&self) -> u8 {
+        self.parts.iter().sum()
+    }
+
+    pub fn is_defeated(&self) -> bool {
+        self.health() == 0
+    }
+
+    /// The boss gets more aggressive once only a single part is left
+    pub fn is_enraged(&self) -> bool {
+        self.parts.iter().filter(|&&health| health > 0).count() <= 1
+    }
+
+    fn speed(&self) -> u8 {
+        match (self.phase, self.is_enraged()) {
+            (Phase::Entering, _) => 2,
+            (Phase::Sweep, false) => 3,
+            (Phase::Sweep, true) => 1,
+            (Phase::Charge, _) => 1,
+            (Phase::Retreat, _) => 2,
+        }
+    }
+
+    fn sweep(&mut self) {
+        if self.descending {
+            if self.y >= BOSS_MAX_Y {
+                self.descending = false;
+            } else {
+                self.y += 1;
+            }
+        } else if self.y <= opps::MIN_SPAWN_Y {
+            self.descending = true;
+        } else {
+            self.y -= 1;
+        }
+    }
+
+    fn step(&mut self) {
+        match self.phase {
+            Phase::Entering => {
+                self.x = self.x.saturating_sub(1);
+                if self.x <= self.hold_x {
+                    self.phase = Phase::Sweep;
+                }
+            }
+            Phase::Sweep => {
+                self.sweep();
+                self.next_charge = self.next_charge.saturating_sub(1);
+                if self.next_charge == 0 {
+                    self.phase = Phase::Charge;
+                }
+            }
+            Phase::Charge => {
+                self.x = self.x.saturating_sub(1);
+                if self.x <= self.hold_x.saturating_sub(CHARGE_DISTANCE) {
+                    self.phase = Phase::Retreat;
+                }
+            }
+            Phase::Retreat => {
+                self.x = self.x.saturating_add(1);
+                if self.x >= self.hold_x {
+                    // every charge brings the boss a little closer
+                    self.hold_x = self.hold_x.saturating_sub(HOLD_ADVANCE);
+                    self.next_charge = if self.is_enraged() {
+                        ENRAGED_CHARGE_INTERVAL
+                    } else {
+                        CHARGE_INTERVAL
+                    };
+                    self.phase = Phase::Sweep;
+                }
+            }
+        }
+    }
+
+    /// Advance the boss, returns true if it reached ferris
+    pub fn tick(&mut self) -> bool {
+        self.next_step = self.next_step.saturating_sub(1);
+        if self.next_step == 0 {
+            self.step();
+            self.next_step = self.speed();
+        }
+
+        self.x == 0
+    }
+
+    /// Send the boss back to the edge of the screen after it reached ferris
+    pub fn knock_back(&mut self) {
+        self.x = opps::SPAWN_OFFSET_X;
+        self.phase = Phase::Entering;
+    }
+
+    fn part_at(&self, y: u8) -> Option<usize> {
+        let part = (y.checked_sub(self.y)? / BOSS_PART_HEIGHT) as usize;
+        (part < BOSS_PARTS).then_some(part)
+    }
+
+    /// Destroyed parts don't block shots anymore
+    pub fn in_row(&self, y: u8) -> bool {
+        self.part_at(y).is_some_and(|part| self.parts[part] > 0)
+    }
+
+    /// Returns true if the shot destroyed a part
+    pub fn hit(&mut self, y: u8) -> bool {
+        let Some(part) = self.part_at(y) else {
+            return false;
+        };
+        let health = &mut self.parts[part];
+        if *health == 0 {
+            return false;
+        }
+        *health -= 1;
+        *health == 0
+    }
+}
+
+impl Drawable for Boss {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        let corner = Size::new(4, 4);
+        // the eyes widen while the boss is charging
+        let eye = match self.phase() {
+            Phase::Charge => 8,
+            _ => 6,
+        };
+        for (i, &health) in self.parts().iter().enumerate() {
+            let top_left = Point::new(
+                self.x() as i32,
+                (self.y() + i as u8 * BOSS_PART_HEIGHT) as i32,
+            );
+            let part = Rectangle::new(
+                top_left,
+                Size::new(BOSS_WIDTH as u32, BOSS_PART_HEIGHT as u32 - 1),
+            );
+            let part = RoundedRectangle::with_equal_corners(part, corner);
+
+            if health > 0 {
+                part.into_styled(gfx::WHITE_FILL).draw(target)?;
+                // eye
+                Circle::new(top_left + Point::new(2, 2), eye)
+                    .into_styled(gfx::BLACK_FILL)
+                    .draw(target)?;
+            } else {
+                // destroyed parts are only drawn as a wreck
+                part.into_styled(gfx::WHITE_LINE).draw(target)?;
+            }
+        }
+
+        // health bar
+        let total = (BOSS_PARTS as u32) * (BOSS_PART_HEALTH as u32);
+        let remaining = HEALTH_BAR_WIDTH * self.health() as u32 / total;
+        Rectangle::new(
+            Point::new(HEALTH_BAR_X, 0),
+            Size::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT),
+        )
+        .into_styled(gfx::WHITE_LINE)
+        .draw(target)?;
+        Rectangle::new(
+            Point::new(HEALTH_BAR_X, 0),
+            Size::new(remaining, HEALTH_BAR_HEIGHT),
+        )
+        .into_styled(gfx::WHITE_FILL)
+        .draw(target)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ticks until the boss is in `phase`, panics if it never gets there
+    fn ticks_until(boss: &mut Boss, phase: Phase) -> u32 {
+        for ticks in 1..10_000 {
+            assert!(!boss.tick(), "the boss reached ferris");
+            if boss.phase() == phase {
+                return ticks;
+            }
+        }
+        panic!("the boss never got to the next phase");
+    }
+
+    #[test]
+    fn phases_follow_each_other() {
+        let mut boss = Boss::new();
+        assert!(boss.phase() == Phase::Entering);
+
+        ticks_until(&mut boss, Phase::Sweep);
+        assert_eq!(boss.distance(), HOLD_X);
+
+        ticks_until(&mut boss, Phase::Charge);
+        ticks_until(&mut boss, Phase::Retreat);
+        assert_eq!(boss.distance(), HOLD_X - CHARGE_DISTANCE);
+
+        ticks_until(&mut boss, Phase::Sweep);
+        assert_eq!(boss.distance(), HOLD_X);
+        // the next charge starts a little closer
+        ticks_until(&mut boss, Phase::Charge);
+        ticks_until(&mut boss, Phase::Retreat);
+        assert_eq!(boss.distance(), HOLD_X - HOLD_ADVANCE - CHARGE_DISTANCE);
+    }
+
+    #[test]
+    fn sweep_stays_reachable() {
+        let mut boss = Boss::new();
+        ticks_until(&mut boss, Phase::Sweep);
+        while boss.phase() == Phase::Sweep {
+            boss.tick();
+            assert!((opps::MIN_SPAWN_Y..=BOSS_MAX_Y).contains(&boss.y()));
+        }
+    }
+
+    #[test]
+    fn knock_back_enters_again() {
+        let mut boss = Boss::new();
+        ticks_until(&mut boss, Phase::Charge);
+        boss.knock_back();
+        assert!(boss.phase() == Phase::Entering);
+        assert_eq!(boss.distance(), opps::SPAWN_OFFSET_X);
+    }
+
+    #[test]
+    fn hits_land_on_the_part_in_the_row() {
+        let mut boss = Boss::new();
+        let top = boss.y();
+        assert_eq!(boss.part_at(top - 1), None);
+        assert_eq!(boss.part_at(top), Some(0));
+        assert_eq!(boss.part_at(top + BOSS_PART_HEIGHT - 1), Some(0));
+        assert_eq!(boss.part_at(top + BOSS_PART_HEIGHT), Some(1));
+        let bottom = top + BOSS_PARTS as u8 * BOSS_PART_HEIGHT;
+        assert_eq!(boss.part_at(bottom - 1), Some(BOSS_PARTS - 1));
+        assert_eq!(boss.part_at(bottom), None);
+
+        assert!(!boss.hit(top - 1));
+        assert!(!boss.hit(bottom));
+        assert_eq!(boss.health(), BOSS_PARTS as u8 * BOSS_PART_HEALTH);
+
+        let middle = top + BOSS_PART_HEIGHT + BOSS_PART_HEIGHT / 2;
+        for _ in 1..BOSS_PART_HEALTH {
+            assert!(!boss.hit(middle));
+        }
+        // only the last hit destroys the part
+        assert!(boss.in_row(middle));
+        assert!(boss.hit(middle));
+        assert_eq!(boss.parts(), &[BOSS_PART_HEALTH, 0, BOSS_PART_HEALTH]);
+
+        // destroyed parts let shots through
+        assert!(!boss.in_row(middle));
+        assert!(!boss.hit(middle));
+        assert!(boss.in_row(top));
+        assert!(!boss.is_defeated());
+    }
+
+    #[test]
+    fn defeated_once_every_part_is_destroyed() {
+        let mut boss = Boss::new();
+        for part in 0..BOSS_PARTS as u8 {
+            for _ in 0..BOSS_PART_HEALTH {
+                boss.hit(boss.y() + part * BOSS_PART_HEIGHT);
+            }
+        }
+        assert!(boss.is_defeated());
+    }
+
+    #[test]
+    fn enraged_boss_charges_sooner() {
+        let mut boss = Boss::new();
+        ticks_until(&mut boss, Phase::Sweep);
+        let calm = ticks_until(&mut boss, Phase::Charge);
+        ticks_until(&mut boss, Phase::Retreat);
+        ticks_until(&mut boss, Phase::Sweep);
+        assert_eq!(ticks_until(&mut boss, Phase::Charge), calm);
+
+        // losing a single part isn't enough
+        let y = boss.y();
+        for _ in 0..BOSS_PART_HEALTH {
+            boss.hit(y);
+        }
+        assert!(!boss.is_enraged());
+        for _ in 0..BOSS_PART_HEALTH {
+            boss.hit(y + BOSS_PART_HEIGHT);
+        }
+        assert!(boss.is_enraged());
+
+        // the charge that is under way finishes as before, the sweeps after it are short and fast
+        ticks_until(&mut boss, Phase::Retreat);
+        ticks_until(&mut boss, Phase::Sweep);
+        let enraged = ticks_until(&mut boss, Phase::Charge);
+        assert_eq!(enraged, ENRAGED_CHARGE_INTERVAL as u32);
+        assert!(enraged < calm);
+    }
+}
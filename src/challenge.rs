@@ -0,0 +1,210 @@
+// seeds for daily and weekly challenges, derived from a date since there is no clock
+
+const FIRST_YEAR: u16 = 2000;
+const LAST_YEAR: u16 = 2099;
+// 2000-01-01 was a saturday, weeks start on monday
+const FIRST_WEEKDAY: u32 = 5;
+pub const CODE_DIGITS: usize = 6;
+pub const DATE_LENGTH: usize = 10;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Period {
+    Daily,
+    /// The whole week the date falls in
+    Weekly,
+}
+
+impl Period {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Period::Daily => "DAILY",
+            Period::Weekly => "WEEKLY",
+        }
+    }
+}
+
+/// The part of the date that is being entered on the device
+#[derive(Clone, Copy, PartialEq)]
+pub enum Field {
+    Year,
+    Month,
+    Day,
+    Period,
+}
+
+impl Field {
+    pub fn next(self) -> Self {
+        match self {
+            Field::Year => Field::Month,
+            Field::Month => Field::Day,
+            Field::Day => Field::Period,
+            Field::Period => Field::Year,
+        }
+    }
+
+    /// First character and length of the field in the date text, `None` for the period
+    pub fn span(&self) -> Option<(usize, usize)> {
+        match self {
+            Field::Year => Some((0, 4)),
+            Field::Month => Some((5, 2)),
+            Field::Day => Some((8, 2)),
+            Field::Period => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Challenge {
+    year: u16,
+    month: u8,
+    day: u8,
+    period: Period,
+}
+
+impl Default for Challenge {
+    fn default() -> Self {
+        Self {
+            year: 2026,
+            month: 1,
+            day: 1,
+            period: Period::Daily,
+        }
+    }
+}
+
+impl Challenge {
+    pub fn new(year: u16, month: u8, day: u8, period: Period) -> Option<Self> {
+        let valid = (FIRST_YEAR..=LAST_YEAR).contains(&year)
+            && (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day);
+        valid.then_some(Self {
+            year,
+            month,
+            day,
+            period,
+        })
+    }
+
+    /// Parse a line like `daily 2026-10-18` or `weekly 2026-10-18`
+    pub fn parse(line: &[u8]) -> Option<Self> {
+        let line = line.trim_ascii();
+        let space = line.iter().position(|&c| c == b' ')?;
+        let (period, date) = (&line[..space], line[space + 1..].trim_ascii());
+        let period = match period {
+            b"daily" => Period::Daily,
+            b"weekly" => Period::Weekly,
+            _ => return None,
+        };
+        if date.len() != DATE_LENGTH || date[4] != b'-' || date[7] != b'-' {
+            return None;
+        }
+        let year = number(&date[..4])?;
+        let month = number(&date[5..7])?;
+        let day = number(&date[8..])?;
+        Self::new(year as u16, month as u8, day as u8, period)
+    }
+
+    pub fn period(&self) -> Period {
+        self.period
+    }
+
+    /// Change a field by one, wrapping around, the day is kept within the month
+    pub fn adjust(&mut self, field: Field, up: bool) {
+        let step = |value: u16, first: u16, last: u16| match (up, value) {
+            (true, value) if value >= last => first,
+            (true, value) => value + 1,
+            (false, value) if value <= first => last,
+            (false, value) => value - 1,
+        };
+        match field {
+            Field::Year => self.year = step(self.year, FIRST_YEAR, LAST_YEAR),
+            Field::Month => self.month = step(self.month as u16, 1, 12) as u8,
+            Field::Day => {
+                let last = days_in_month(self.year, self.month) as u16;
+                self.day = step(self.day as u16, 1, last) as u8;
+            }
+            Field::Period => {
+                self.period = match self.period {
+                    Period::Daily => Period::Weekly,
+                    Period::Weekly => Period::Daily,
+                }
+            }
+        }
+        self.day = u8::min(self.day, days_in_month(self.year, self.month));
+    }
+
+    /// The date as `YYYY-MM-DD`
+    pub fn date(&self) -> [u8; DATE_LENGTH] {
+        let mut text = *b"0000-00-00";
+        write_digits(&mut text[..4], self.year as u32);
+        write_digits(&mut text[5..7], self.month as u32);
+        write_digits(&mut text[8..], self.day as u32);
+        text
+    }
+
+    /// Days since 2000-01-01
+    fn days(&self) -> u32 {
+        let years = (FIRST_YEAR..self.year).map(|year| 365 + is_leap_year(year) as u32);
+        let months = (1..self.month).map(|month| days_in_month(self.year, month) as u32);
+        years.sum::<u32>() + months.sum::<u32>() + self.day as u32 - 1
+    }
+
+    /// The same for every date of the period, different for every other period
+    pub fn seed(&self) -> u32 {
+        match self.period {
+            Period::Daily => mix(self.days()),
+            Period::Weekly => mix(((self.days() + FIRST_WEEKDAY) / 7) | 1 << 31),
+        }
+    }
+}
+
+/// Short code that ties a result to the challenge it was played in
+pub fn verification_code(seed: u32, score: u32, ticks: u32) -> u32 {
+    mix(seed ^ mix(score ^ mix(ticks))) & ((1 << (CODE_DIGITS * 4)) - 1)
+}
+
+/// The code in upper case hex
+pub fn format_code(code: u32) -> [u8; CODE_DIGITS] {
+    let mut text = [0; CODE_DIGITS];
+    for (i, digit) in text.iter_mut().rev().enumerate() {
+        *digit = b"0123456789ABCDEF"[(code >> (i * 4)) as usize & 0xf];
+    }
+    text
+}
+
+// murmur3 finalizer, spreads neighbouring dates over the whole range
+fn mix(mut value: u32) -> u32 {
+    value ^= value >> 16;
+    value = value.wrapping_mul(0x85eb_ca6b);
+    value ^= value >> 13;
+    value = value.wrapping_mul(0xc2b2_ae35);
+    value ^ (value >> 16)
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn number(digits: &[u8]) -> Option<u32> {
+    digits.iter().try_fold(0, |number: u32, &digit| {
+        digit
+            .is_ascii_digit()
+            .then(|| number * 10 + (digit - b'0') as u32)
+    })
+}
+
+fn write_digits(text: &mut [u8], mut value: u32) {
+    for digit in text.iter_mut().rev() {
+        *digit = b'0' + (value % 10) as u8;
+        value /= 10;
+    }
+}
@@ -0,0 +1,262 @@
+use crate::opps;
+use crate::stats::RunStats;
+
+// ticks between two looks at how the player is doing
+const INTERVAL: u32 = 50;
+// the difficulty never strays further than this many levels from the tier
+pub const MAX_LEVEL: i8 = 3;
+// every level makes spawns this much faster, in percent, and below the tier steps too
+const PACE_PERCENT: i8 = 10;
+// every level adds this to the chance of a gunner, out of 256
+const GUNNERS_PER_LEVEL: i8 = 32;
+// accuracy in percent over the last interval, with at least a few shots to judge by
+const GOOD_ACCURACY: u32 = 50;
+const POOR_ACCURACY: u32 = 30;
+const MIN_SHOTS: u32 = 3;
+// nothing came closer than this, the player has the lawn under control
+const SAFE_DISTANCE: u8 = 64;
+// not having to reload for this long while hitting is another sign of that
+const RELAXED_TICKS: u32 = 100;
+
+/// How the opponents differ from their tier, applied by the lawn
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub struct Adjustment {
+    /// Percent of ticks between spawns added, negative spawns faster
+    pub spawn_rate: i8,
+    /// Percent of ticks per step added, negative moves faster
+    pub speed: i8,
+    /// Added health, opponents always keep at least one
+    pub health: i8,
+    /// Added chance of an opponent being a gunner, out of 256
+    pub gunners: i8,
+}
+
+impl Adjustment {
+    fn from_level(level: i8) -> Self {
+        // tougher or faster opponents only feed a good player more points per kill,
+        // and extra lives with them, so they only ever get weaker
+        let easing = i8::min(0, level);
+        Self {
+            spawn_rate: -level * PACE_PERCENT,
+            speed: -easing * PACE_PERCENT,
+            health: easing / 2,
+            gunners: level * GUNNERS_PER_LEVEL,
+        }
+    }
+}
+
+/// What the director saw when it changed the difficulty
+#[derive(Clone, Copy, Debug)]
+pub struct Change {
+    /// Ticks on the lawn when it changed
+    pub tick: u32,
+    pub level: i8,
+    /// Hits per shot in percent over the last interval, `None` with too few shots
+    pub accuracy: Option<u32>,
+    /// The closest anything got to ferris over the last interval, 0 if it got through
+    pub closest: u8,
+    pub since_reload: u32,
+    /// Whether ferris got hurt over the last interval
+    pub hurt: bool,
+    pub adjustment: Adjustment,
+}
+
+/// Nudges the difficulty up or down within bounds, depending on how well the game goes
+///
+/// Every few seconds it looks at the accuracy, how close opponents got,
+/// whether ferris got hurt and how long it has been since the last reload.
+/// Doing well on most of them makes the lawn one level harder, struggling
+/// makes it one level easier. Hardly shooting at what is on the lawn counts
+/// as struggling too.
+pub struct Director {
+    level: i8,
+    next_check: u32,
+    // counts at the last check
+    shots: u32,
+    hits: u32,
+    reloads: u32,
+    closest: u8,
+    // something was on the lawn during the interval
+    busy: bool,
+    hurt: bool,
+    last_reload: u32,
+    changes: u32,
+    last_change: Option<Change>,
+}
+
+impl Default for Director {
+    fn default() -> Self {
+        Self {
+            level: 0,
+            next_check: INTERVAL,
+            shots: 0,
+            hits: 0,
+            reloads: 0,
+            closest: u8::MAX,
+            busy: false,
+            hurt: false,
+            last_reload: 0,
+            changes: 0,
+            last_change: None,
+        }
+    }
+}
+
+impl Director {
+    /// Call once per tick on the lawn, returns the change if the difficulty changed
+    pub fn tick(&mut self, stats: &RunStats, closest: Option<u8>, hurt: bool) -> Option<Change> {
+        self.busy |= closest.is_some();
+        self.hurt |= hurt;
+        let closest = if hurt { Some(0) } else { closest };
+        self.closest = u8::min(self.closest, closest.unwrap_or(u8::MAX));
+        if stats.reloads != self.reloads {
+            self.reloads = stats.reloads;
+            self.last_reload = stats.ticks;
+        }
+
+        if stats.ticks < self.next_check {
+            return None;
+        }
+        self.next_check = stats.ticks + INTERVAL;
+
+        let shots = stats.shots - self.shots;
+        let accuracy = (shots >= MIN_SHOTS).then(|| (stats.hits - self.hits) * 100 / shots);
+        let since_reload = stats.ticks - self.last_reload;
+        let closest = self.closest;
+        let busy = self.busy;
+        let hurt = self.hurt;
+        self.shots = stats.shots;
+        self.hits = stats.hits;
+        self.closest = u8::MAX;
+        self.busy = false;
+        self.hurt = false;
+
+        let mut verdict = 0;
+        match accuracy {
+            Some(accuracy) if accuracy >= GOOD_ACCURACY => verdict += 1,
+            Some(accuracy) if accuracy <= POOR_ACCURACY => verdict -= 1,
+            Some(_) => (),
+            // barely shooting back at what is coming
+            None if busy => verdict -= 1,
+            None => (),
+        }
+        if closest <= opps::DANGER_DISTANCE {
+            verdict -= 1;
+        } else if closest >= SAFE_DISTANCE {
+            verdict += 1;
+        }
+        // enough on its own, whatever else went well
+        if hurt {
+            verdict = i32::min(verdict, -2);
+        }
+        let hitting = accuracy.is_some_and(|accuracy| accuracy > POOR_ACCURACY);
+        if since_reload >= RELAXED_TICKS && hitting {
+            verdict += 1;
+        }
+
+        // one sign alone isn't enough to go by
+        let level = match verdict {
+            2.. => self.level + 1,
+            ..=-2 => self.level - 1,
+            _ => self.level,
+        }
+        .clamp(-MAX_LEVEL, MAX_LEVEL);
+        if level == self.level {
+            return None;
+        }
+
+        self.level = level;
+        self.changes += 1;
+        let change = Change {
+            tick: stats.ticks,
+            level,
+            accuracy,
+            closest,
+            since_reload,
+            hurt,
+            adjustment: self.adjustment(),
+        };
+        self.last_change = Some(change);
+        Some(change)
+    }
+
+    /// Levels above (or below) the tier, between -MAX_LEVEL and MAX_LEVEL
+    pub fn level(&self) -> i8 {
+        self.level
+    }
+
+    pub fn adjustment(&self) -> Adjustment {
+        Adjustment::from_level(self.level)
+    }
+
+    /// How often the difficulty changed this game
+    pub fn changes(&self) -> u32 {
+        self.changes
+    }
+
+    pub fn last_change(&self) -> Option<&Change> {
+        self.last_change.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // one interval of play, `hits` of `shots` and something at `closest` the whole time
+    fn play(director: &mut Director, stats: &mut RunStats, shots: u32, hits: u32, closest: u8) {
+        stats.shots += shots;
+        stats.hits += hits;
+        for _ in 0..INTERVAL {
+            stats.ticks += 1;
+            director.tick(stats, Some(closest), false);
+        }
+    }
+
+    #[test]
+    fn not_shooting_back_eases_off() {
+        let mut director = Director::default();
+        let mut stats = RunStats::default();
+        play(&mut director, &mut stats, 0, 0, opps::DANGER_DISTANCE);
+        assert_eq!(director.level(), -1);
+    }
+
+    #[test]
+    fn getting_hurt_eases_off() {
+        let mut director = Director::default();
+        let mut stats = RunStats::default();
+        stats.ticks += 1;
+        director.tick(&stats, None, true);
+        play(&mut director, &mut stats, 4, 2, SAFE_DISTANCE);
+        assert_eq!(director.level(), -1);
+    }
+
+    #[test]
+    fn missing_without_reloading_is_not_relaxed() {
+        let mut director = Director::default();
+        let mut stats = RunStats::default();
+        for _ in 0..4 {
+            play(&mut director, &mut stats, 4, 0, SAFE_DISTANCE);
+        }
+        assert_eq!(director.level(), 0);
+    }
+
+    #[test]
+    fn doing_well_makes_it_harder() {
+        let mut director = Director::default();
+        let mut stats = RunStats::default();
+        play(&mut director, &mut stats, 4, 4, SAFE_DISTANCE);
+        assert_eq!(director.level(), 1);
+        assert!(director.adjustment().gunners > 0);
+        assert!(director.adjustment().spawn_rate < 0);
+    }
+
+    #[test]
+    fn harder_levels_never_make_opponents_tougher() {
+        for level in 0..=MAX_LEVEL {
+            let adjustment = Adjustment::from_level(level);
+            assert_eq!(adjustment.health, 0);
+            assert_eq!(adjustment.speed, 0);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use waveshare_rp2040_zero::hal::rom_data;
+
+// the last sector of the 2MB flash, far away from the firmware
+const FLASH_SIZE: u32 = 2048 * 1024;
+const SECTOR_SIZE: u32 = 4096;
+pub const PAGE_SIZE: usize = 256;
+const RECORD_OFFSET: u32 = FLASH_SIZE - SECTOR_SIZE;
+const XIP_BASE: u32 = 0x1000_0000;
+// the second stage bootloader at the start of flash, it sets up the fast QSPI reads
+const BOOT2_SIZE: usize = 256;
+
+// erase the whole sector at once, with the 64K block erase command as in the SDK
+const BLOCK_SIZE: u32 = 1 << 16;
+const BLOCK_CMD: u8 = 0xd8;
+
+/// The page that holds the saved record, read through XIP
+pub fn read() -> &'static [u8; PAGE_SIZE] {
+    unsafe { &*((XIP_BASE + RECORD_OFFSET) as *const [u8; PAGE_SIZE]) }
+}
+
+/// Replace the saved record
+///
+/// While the flash is busy nothing can run from it, interrupts are off for the duration.
+/// Afterwards XIP is set up again by a copy of boot2, as the pico-sdk does, the bootrom
+/// alone would leave it in the slow command mode.
+pub fn write(data: &[u8; PAGE_SIZE]) {
+    // word aligned, it is called as code
+    let mut boot2 = [0u32; BOOT2_SIZE / 4];
+    for (i, word) in boot2.iter_mut().enumerate() {
+        *word = unsafe { core::ptr::read_volatile((XIP_BASE as *const u32).add(i)) };
+    }
+    let rom = Rom {
+        connect_internal_flash: rom_data::connect_internal_flash::ptr(),
+        flash_exit_xip: rom_data::flash_exit_xip::ptr(),
+        flash_range_erase: rom_data::flash_range_erase::ptr(),
+        flash_range_program: rom_data::flash_range_program::ptr(),
+        flash_flush_cache: rom_data::flash_flush_cache::ptr(),
+        // the thumb bit set
+        boot2: unsafe {
+            core::mem::transmute::<usize, extern "C" fn()>(boot2.as_ptr() as usize + 1)
+        },
+    };
+    critical_section::with(|_| unsafe {
+        write_from_ram(&rom, data);
+    });
+}
+
+// looked up beforehand, the lookup itself runs from flash
+struct Rom {
+    connect_internal_flash: unsafe extern "C" fn(),
+    flash_exit_xip: unsafe extern "C" fn(),
+    flash_range_erase: unsafe extern "C" fn(u32, usize, u32, u8),
+    flash_range_program: unsafe extern "C" fn(u32, *const u8, usize),
+    flash_flush_cache: unsafe extern "C" fn(),
+    boot2: extern "C" fn(),
+}
+
+#[inline(never)]
+#[link_section = ".data.ram_func"]
+unsafe fn write_from_ram(rom: &Rom, data: &[u8; PAGE_SIZE]) {
+    (rom.connect_internal_flash)();
+    (rom.flash_exit_xip)();
+    (rom.flash_range_erase)(RECORD_OFFSET, SECTOR_SIZE as usize, BLOCK_SIZE, BLOCK_CMD);
+    (rom.flash_range_program)(RECORD_OFFSET, data.as_ptr(), data.len());
+    (rom.flash_flush_cache)();
+    (rom.boot2)();
+}
@@ -1,25 +1,75 @@
+use crate::boss;
+use crate::challenge::{self, Challenge, Field};
+use crate::director::Director;
 use crate::gfx;
-use crate::guns::{self, Gun, Revolver, Scorpio};
-use crate::opps::{self, Lawn};
-use fugit::Duration;
+use crate::guns::{self, Bullet, Primary, Revolver, Weapon};
+use crate::lifetime::{Achievement, Lifetime};
+use crate::modes::GameMode;
+use crate::opps::{self, Lawn, ShotResult};
+use crate::pickups::{self, Pickups};
+use crate::powerups::{self, PowerUp, PowerUps};
+use crate::rng::XorShift;
+use crate::score::{self, Breakdown, Combo};
+use crate::stats::RunStats;
+use core::iter::Flatten;
+use core::slice;
+use embedded_graphics::prelude::OriginDimensions;
+use fugit::MicrosDurationU64;
 use rand_core::RngCore;
 
 pub const START_Y: u8 = 18;
-const STEP_Y: u8 = 2;
+pub const STEP_Y: u8 = 2;
 
-// this is not 100ms, I don't know how to configure this properly
-pub const TICK_INTERVAL: Duration<u64, 1, 8> = Duration::<u64, 1, 8>::millis(100);
+// in the resolution of the hardware timer
+pub const TICK_INTERVAL: MicrosDurationU64 = MicrosDurationU64::millis(100);
 pub const DEBOUNCE_TICKS: u8 = 1;
 
+pub const START_LIVES: u8 = 3;
+pub const MAX_LIVES: u8 = 5;
+// an extra life is awarded every time the score passes a multiple of this
+pub const EXTRA_LIFE_INTERVAL: u32 = 25;
+const INVULNERABLE_TICKS: u8 = 20;
+const BREACH_RUMBLE_TICKS: u8 = 5;
+const BREACH_RUMBLE_DIVIDER: u16 = 1;
+// holding shoot this long on the reload screen ejects all spent casings
+const EJECT_HOLD_TICKS: u8 = 5;
+const EJECT_RUMBLE_TICKS: u8 = 2;
+const EJECT_RUMBLE_DIVIDER: u16 = 3;
+// cartridges that are added to the reserve for every point of health a killed opponent had,
+// so a player who never misses never runs dry
+const KILL_CARTRIDGES: u8 = 1;
+const BOSS_CARTRIDGES: u8 = 6;
+const PICKUP_CARTRIDGES: u8 = 6;
+// holding reload this long switches between the primary weapon and the revolver
+pub const SWITCH_HOLD_TICKS: u8 = 4;
+const SWITCH_RUMBLE_TICKS: u8 = 1;
+const SWITCH_RUMBLE_DIVIDER: u16 = 2;
+// ticks per on/off phase of blinking HUD elements
+const BLINK_TICKS: u8 = 4;
+// pages of statistics on the game over screen
+const STATS_PAGES: u8 = 3;
+// lifetime counters, the best result per mode and the list of achievements
+const RECORDS_PAGES: u8 = 3;
+// how long an achievement is shown after it's unlocked
+const TOAST_TICKS: u8 = 30;
+// kills it takes for some of the achievements
+const BURST_KILLS: u8 = 3;
+const SKEWER_KILLS: u32 = 3;
+const UNTOUCHABLE_SCORE: u32 = 50;
+
 // quick sanity check so we don't spawn unreachable opponents
 static_assertions::const_assert!(gfx::FERRIS_MAX_Y + guns::REVOLVER_OFFSET >= opps::MAX_SPAWN_Y);
 static_assertions::const_assert!(gfx::FERRIS_MAX_Y + guns::SCORPIO_OFFSET >= opps::MAX_SPAWN_Y);
+static_assertions::const_assert!(gfx::FERRIS_MAX_Y + guns::SHOTGUN_OFFSET >= opps::MAX_SPAWN_Y);
+static_assertions::const_assert!(gfx::FERRIS_MAX_Y + guns::SNIPER_OFFSET >= opps::MAX_SPAWN_Y);
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum Direction {
     Clockwise,
     CounterClock,
 }
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum Button {
     ReloadToggle,
     Shoot,
@@ -37,6 +87,12 @@ pub enum Screen {
     Normal,
     Reload,
     Wasted,
+    /// Lifetime statistics and achievements, held reload on the start screen
+    Records,
+    /// Entering the date of a challenge before it starts
+    Challenge,
+    /// Everything on the lawn stands still until the game is resumed or quit
+    Paused,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -47,15 +103,45 @@ pub enum Rumble {
 
 pub struct Game {
     screen: Screen,
+    // where to go back to when the game is resumed
+    paused_from: Screen,
+    quit_selected: bool,
     score: u32,
     y: u8,
+    lives: u8,
+    invulnerable: u8,
+    frame: u8,
 
-    primary_gun: Option<Scorpio>,
+    primary_gun: Option<Primary>,
+    holstered: bool,
     pub secondary_gun: Revolver,
     next_shot: Option<u8>,
+    bullets: [Option<Bullet>; 8],
     pub lawn: opps::Lawn,
+    // spawns on the lawn, seeded so the same seed gets the same opponents
+    random: XorShift,
+    pickups: Pickups,
+    power_ups: PowerUps,
+    combo: Combo,
+    breakdown: Breakdown,
+    stats: RunStats,
+    page: u8,
+    lifetime: Lifetime,
+    unsaved: bool,
+    toast: Option<(Achievement, u8)>,
+    burst_kills: u8,
     reload_toggle_debounce: u8,
     shoot_debounce: u8,
+    shoot_held: Option<u8>,
+    reload_held: Option<u8>,
+    realism: bool,
+    adaptive: bool,
+    // only around while playing with adaptive difficulty
+    director: Option<Director>,
+    mode: GameMode,
+    challenge: Challenge,
+    challenge_field: Field,
+    seed: u32,
 
     pub rumble: Option<Rumble>,
 }
@@ -64,16 +150,43 @@ impl Default for Game {
     fn default() -> Self {
         Game {
             screen: Screen::Start,
+            paused_from: Screen::Normal,
+            quit_selected: false,
             score: 0,
             y: START_Y,
+            lives: START_LIVES,
+            invulnerable: 0,
+            frame: 0,
 
-            // primary_gun: Some(Scorpio::new()),
+            // primary_gun: Some(Primary::scorpio()),
             primary_gun: None,
+            holstered: false,
             secondary_gun: Revolver::new(),
             next_shot: None,
+            bullets: [None, None, None, None, None, None, None, None],
             lawn: Lawn::default(),
+            random: XorShift::new(0),
+            pickups: Pickups::new(0, GameMode::Endless),
+            power_ups: PowerUps::default(),
+            combo: Combo::default(),
+            breakdown: Breakdown::default(),
+            stats: RunStats::default(),
+            page: 0,
+            lifetime: Lifetime::default(),
+            unsaved: false,
+            toast: None,
+            burst_kills: 0,
             reload_toggle_debounce: 0,
             shoot_debounce: 0,
+            shoot_held: None,
+            reload_held: None,
+            realism: false,
+            adaptive: false,
+            director: None,
+            mode: GameMode::Endless,
+            challenge: Challenge::default(),
+            challenge_field: Field::Year,
+            seed: 0,
 
             rumble: None,
         }
@@ -85,6 +198,46 @@ impl Game {
         Game::default()
     }
 
+    /// Continue counting from what was saved before
+    pub fn with_lifetime(mut self, lifetime: Lifetime) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// Preselect a mode on the start screen
+    pub fn with_mode(mut self, mode: GameMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Let the difficulty follow how well the games go
+    pub fn with_adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    pub fn lifetime(&self) -> &Lifetime {
+        &self.lifetime
+    }
+
+    /// The lifetime counters, once after every game that ended
+    pub fn take_unsaved(&mut self) -> Option<Lifetime> {
+        let unsaved = self.unsaved;
+        self.unsaved = false;
+        unsaved.then_some(self.lifetime)
+    }
+
+    /// The achievement that was just unlocked, for a little while
+    pub fn toast(&self) -> Option<Achievement> {
+        self.toast.map(|(achievement, _)| achievement)
+    }
+
+    fn unlock(&mut self, achievement: Achievement) {
+        if self.lifetime.unlock(achievement) {
+            self.toast = Some((achievement, TOAST_TICKS));
+        }
+    }
+
     pub fn screen(&self) -> Screen {
         self.screen
     }
@@ -93,31 +246,363 @@ impl Game {
         self.y
     }
 
-    pub fn gun(&mut self) -> Gun<'_> {
-        self.primary_gun
-            .as_mut()
-            .map(Gun::Scorpio)
-            .unwrap_or(Gun::Revolver(&mut self.secondary_gun))
+    /// If the revolver needs manual handling, toggled on the start screen
+    pub fn is_realism(&self) -> bool {
+        self.realism
+    }
+
+    /// If the next game gets adaptive difficulty, challenges never do
+    pub fn is_adaptive(&self) -> bool {
+        self.adaptive
+    }
+
+    /// Only takes effect when the next game starts
+    pub fn toggle_adaptive(&mut self) {
+        self.adaptive = !self.adaptive;
+    }
+
+    /// Nudging the difficulty of the current game, with adaptive difficulty
+    pub fn director(&self) -> Option<&Director> {
+        self.director.as_ref()
+    }
+
+    /// Picked with the encoder on the start screen
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// The date of the last challenge, entered on the device or pushed over serial
+    pub fn challenge(&self) -> &Challenge {
+        &self.challenge
+    }
+
+    /// The part of the challenge date that is being entered
+    pub fn challenge_field(&self) -> Field {
+        self.challenge_field
+    }
+
+    /// Pick the challenge mode with the given date, only while nothing is being played
+    pub fn set_challenge(&mut self, challenge: Challenge) {
+        if let Screen::Start | Screen::Challenge = self.screen {
+            self.challenge = challenge;
+            self.mode = GameMode::Challenge;
+        }
+    }
+
+    /// Stop everything on the lawn, also requested by the USB host
+    pub fn pause(&mut self) {
+        if let Screen::Normal | Screen::Reload = self.screen {
+            self.paused_from = self.screen;
+            self.quit_selected = false;
+            self.screen = Screen::Paused;
+        }
+    }
+
+    /// If quit rather than resume is selected on the pause screen
+    pub fn is_quit_selected(&self) -> bool {
+        self.quit_selected
+    }
+
+    /// Ties the result of a challenge to its date, for checking it with the host-side tools
+    pub fn verification_code(&self) -> Option<u32> {
+        (self.mode == GameMode::Challenge)
+            .then(|| challenge::verification_code(self.seed, self.score, self.stats.ticks))
+    }
+
+    /// Time left in a time attack or points made in a sprint, out of the full amount
+    pub fn mode_progress(&self) -> Option<(u32, u32)> {
+        if let Some(limit) = self.mode.time_limit() {
+            return Some((limit.saturating_sub(self.stats.ticks), limit));
+        }
+        let target = self.mode.target_score()?;
+        Some((u32::min(self.score, target), target))
+    }
+
+    /// What the game is ranked by in its mode, `None` for a sprint that didn't finish
+    pub fn result(&self) -> Option<u32> {
+        match self.mode.target_score() {
+            Some(target) if self.score < target => None,
+            Some(_) => Some(self.stats.ticks),
+            None => Some(self.score),
+        }
+    }
+
+    pub fn lives(&self) -> u8 {
+        self.lives
+    }
+
+    /// Ferris blinks while invulnerable after getting hit
+    pub fn is_ferris_visible(&self) -> bool {
+        (self.invulnerable / 2).is_multiple_of(2)
+    }
+
+    /// Phase for HUD elements that blink to get attention
+    pub fn is_blink_on(&self) -> bool {
+        (self.frame / BLINK_TICKS).is_multiple_of(2)
+    }
+
+    fn hurt(&mut self, cause: opps::Cause) {
+        if self.invulnerable > 0 {
+            return;
+        }
+
+        self.stats.add_damage(cause);
+        self.lives = self.lives.saturating_sub(1);
+        if self.lives == 0 {
+            self.stats.death = Some(cause);
+            self.game_over();
+            return;
+        }
+
+        self.invulnerable = INVULNERABLE_TICKS;
+        self.rumble = Some(Rumble::Start((BREACH_RUMBLE_TICKS, BREACH_RUMBLE_DIVIDER)));
+    }
+
+    fn game_over(&mut self) {
+        // several things can end the game during the same tick
+        if self.screen == Screen::Wasted {
+            return;
+        }
+        self.screen = Screen::Wasted;
+        self.page = 0;
+        self.lifetime.record(self.mode, &self.stats, self.result());
+        self.unsaved = true;
+    }
+
+    /// The weapon ferris is currently holding
+    pub fn gun(&self) -> &dyn Weapon {
+        match &self.primary_gun {
+            Some(primary) if !self.holstered => primary.weapon(),
+            _ => &self.secondary_gun,
+        }
+    }
+
+    fn gun_mut(&mut self) -> &mut dyn Weapon {
+        match &mut self.primary_gun {
+            Some(primary) if !self.holstered => primary.weapon_mut(),
+            _ => &mut self.secondary_gun,
+        }
+    }
+
+    /// The weapon that is carried but not in use, if any
+    pub fn holstered_gun(&self) -> Option<&dyn Weapon> {
+        let primary = self.primary_gun.as_ref()?;
+        Some(if self.holstered {
+            primary.weapon()
+        } else {
+            &self.secondary_gun
+        })
+    }
+
+    fn switch_gun(&mut self) {
+        if self.primary_gun.is_none() {
+            return;
+        }
+        self.holstered = !self.holstered;
+        // don't keep autofiring with the other weapon
+        self.next_shot = None;
+        self.rumble = Some(Rumble::Start((SWITCH_RUMBLE_TICKS, SWITCH_RUMBLE_DIVIDER)));
+    }
+
+    fn drop_primary(&mut self) {
+        self.primary_gun = None;
+        self.holstered = false;
+    }
+
+    fn pick_up(&mut self, primary: Primary) {
+        self.primary_gun = Some(primary);
+        self.holstered = false;
     }
 
     pub fn shoot(&mut self) {
-        match self.gun().shoot() {
+        let y = self.y;
+        let primary_active = self.primary_gun.is_some() && !self.holstered;
+        let gun = self.gun_mut();
+        match gun.shoot() {
             // did fire
-            Some((true, offset)) => {
-                if self.lawn.shoot(self.y + offset) {
-                    self.add_score(1);
+            Some(true) => {
+                let kind = gun.kind();
+                let barrel = (y + gun.offset()) as i16;
+                let mut rays = [0u8; guns::MAX_RAYS];
+                for (ray, offset) in rays.iter_mut().zip(gun.rays()) {
+                    *ray = (barrel + *offset as i16).max(0) as u8;
+                }
+                let rays = &rays[..usize::min(gun.rays().len(), guns::MAX_RAYS)];
+                let muzzle = gun.sprite().size().width as u8;
+                let bullet_speed = gun.bullet_speed();
+                let penetration = gun.penetration();
+                let rumble = gun.rumble();
+                // put the primary weapon away as soon as it's used up
+                if gun.ammo() == 0 && primary_active {
+                    self.drop_primary();
+                }
+
+                let mut result = ShotResult::default();
+                let mut in_flight = false;
+                for &ray in rays {
+                    let free = self.bullets.iter_mut().find(|slot| slot.is_none());
+                    match (bullet_speed, free) {
+                        (Some(speed), Some(slot)) => {
+                            *slot = Some(Bullet::new(muzzle, ray, speed, penetration, kind));
+                            in_flight = true;
+                        }
+                        // hitscan, also if there are too many bullets in flight
+                        _ => {
+                            result.add(self.lawn.shoot(&[ray], penetration));
+                        }
+                    }
+                }
+                // bullets that are still in flight can still hit
+                self.stats.shots += 1;
+                if result.is_hit() {
+                    self.stats.hits += 1;
+                } else if !in_flight {
+                    self.combo.miss();
+                }
+                self.credit_kills(kind, result.kills);
+                if result.kills >= SKEWER_KILLS {
+                    self.unlock(Achievement::Skewer);
                 }
-                self.rumble = Some(Rumble::Start((
-                    guns::SHOT_RUMBLE_TICKS,
-                    guns::SHOT_RUMBLE_DIVIDER,
-                )));
+                self.score_shot(&result);
+
+                // hits are felt a little longer
+                let (duration, divider) = rumble;
+                let duration = duration + result.is_hit() as u8;
+                self.rumble = Some(Rumble::Start((duration, divider)));
+            }
+            // pulling the trigger on an empty chamber breaks the combo
+            Some(false) if self.gun().is_reloadable() && !self.secondary_gun.is_cocked() => {
+                self.stats.dry_fires += 1;
+                self.combo.miss();
             }
             // did not fire (but gun is not used up)
-            Some((false, _)) => (),
+            Some(false) => (),
             // primary weapon is used up
             None => {
-                self.primary_gun = None;
+                self.drop_primary();
+            }
+        }
+    }
+
+    fn score_shot(&mut self, result: &ShotResult) {
+        if result.is_hit() {
+            self.combo.hit(result.hits + result.boss_hits);
+        }
+        self.stats.kills += result.kills;
+        self.stats.max_combo = u8::max(self.stats.max_combo, self.combo.multiplier());
+        if self.combo.multiplier() == score::MAX_MULTIPLIER {
+            self.unlock(Achievement::MaxCombo);
+        }
+        let multiplier = self.combo.multiplier() as u32;
+        let kills = result.kills + result.boss_parts;
+        let range = result.long_range * score::LONG_RANGE_BONUS;
+        let precision = result.precise * score::PRECISION_BONUS;
+        self.breakdown.kills += kills;
+        self.breakdown.range += range;
+        self.breakdown.precision += precision;
+        self.breakdown.combo += (kills + range + precision) * (multiplier - 1);
+        self.add_score((kills + range + precision) * multiplier);
+
+        let cartridges = (result.toughness as u8).saturating_mul(KILL_CARTRIDGES);
+        self.secondary_gun.add_reserve(cartridges);
+        if result.boss {
+            self.secondary_gun.add_reserve(BOSS_CARTRIDGES);
+            self.unlock(Achievement::BossSlayer);
+            self.breakdown.boss += boss::BOSS_BONUS;
+            self.add_score(boss::BOSS_BONUS);
+            // defeating a boss always drops a scorpio, unless the mode has no weapon drops
+            if self.mode.drops(pickups::Kind::Scorpio) {
+                self.pick_up(Primary::scorpio());
+            }
+        }
+        if let Some((x, y)) = result.dropped_at {
+            self.pickups.roll(x, y, self.score);
+        }
+    }
+
+    fn credit_kills(&mut self, kind: guns::Kind, kills: u32) {
+        self.stats.add_kills(kind, kills);
+        if kind == guns::Kind::Scorpio {
+            self.burst_kills = self.burst_kills.saturating_add(kills as u8);
+            if self.burst_kills >= BURST_KILLS {
+                self.unlock(Achievement::Burst);
+            }
+        }
+    }
+
+    pub fn combo(&self) -> &Combo {
+        &self.combo
+    }
+
+    pub fn stats(&self) -> &RunStats {
+        &self.stats
+    }
+
+    /// The page that is shown on screens that scroll with the encoder
+    pub fn page(&self) -> u8 {
+        self.page
+    }
+
+    pub fn pages(&self) -> u8 {
+        match self.screen {
+            Screen::Wasted => STATS_PAGES,
+            Screen::Records => RECORDS_PAGES,
+            _ => 1,
+        }
+    }
+
+    /// Where the points came from, shown when the game is over
+    pub fn breakdown(&self) -> &Breakdown {
+        &self.breakdown
+    }
+
+    pub fn pickups(&self) -> &Pickups {
+        &self.pickups
+    }
+
+    pub fn power_ups(&self) -> &PowerUps {
+        &self.power_ups
+    }
+
+    fn collect(&mut self, kind: pickups::Kind) {
+        match kind {
+            pickups::Kind::Ammo => self.secondary_gun.add_reserve(PICKUP_CARTRIDGES),
+            pickups::Kind::Life => self.lives = u8::min(self.lives + 1, MAX_LIVES),
+            pickups::Kind::PowerUp(PowerUp::ScreenClear) => {
+                let result = self.lawn.clear();
+                self.score_shot(&result);
+            }
+            pickups::Kind::PowerUp(power_up) => self.power_ups.activate(power_up),
+            _ => {
+                if let Some(primary) = kind.primary() {
+                    self.pick_up(primary);
+                }
+            }
+        }
+    }
+
+    pub fn bullets(&self) -> Flatten<slice::Iter<'_, Option<Bullet>>> {
+        self.bullets.iter().flatten()
+    }
+
+    fn tick_bullets(&mut self) {
+        for i in 0..self.bullets.len() {
+            let Some(bullet) = &mut self.bullets[i] else {
+                continue;
+            };
+            let kind = bullet.kind();
+            let travelled = bullet.tick();
+            let result = self.lawn.sweep(bullet.y(), travelled, bullet.penetration());
+            if bullet.absorb(result.hits + result.boss_hits) {
+                if bullet.has_struck() {
+                    self.stats.hits += 1;
+                } else {
+                    self.combo.miss();
+                }
+                self.bullets[i] = None;
             }
+            self.credit_kills(kind, result.kills);
+            self.score_shot(&result);
         }
     }
 
@@ -126,18 +611,28 @@ impl Game {
     }
 
     pub fn add_score(&mut self, points: u32) {
+        let previous = self.score;
         self.score = self.score.saturating_add(points);
-        if self.primary_gun.is_some() {
-            return;
+
+        // extra life
+        if previous / EXTRA_LIFE_INTERVAL < self.score / EXTRA_LIFE_INTERVAL {
+            self.lives = u8::min(self.lives + 1, MAX_LIVES);
+        }
+
+        if self.score >= UNTOUCHABLE_SCORE && self.stats.damage == 0 {
+            self.unlock(Achievement::Untouchable);
         }
 
-        // bonus weapon drop
-        if self.score % 10 == 0 {
-            self.primary_gun = Some(Scorpio::new());
+        if self
+            .mode
+            .target_score()
+            .is_some_and(|target| self.score >= target)
+        {
+            self.game_over();
         }
     }
 
-    pub fn tick<R: RngCore>(&mut self, random: R) {
+    pub fn tick<R: RngCore>(&mut self, mut random: R) {
         if self.reload_toggle_debounce > 0 {
             self.reload_toggle_debounce -= 1;
         }
@@ -146,11 +641,90 @@ impl Game {
             self.shoot_debounce -= 1;
         }
 
+        self.frame = self.frame.wrapping_add(1);
+
+        // nothing moves and no timer runs out while paused
+        if self.screen == Screen::Paused {
+            return;
+        }
+
         if let Some(Rumble::On(duration)) = &mut self.rumble {
             *duration -= 1;
         }
 
+        if self.invulnerable > 0 {
+            self.invulnerable -= 1;
+        }
+
+        if let Some((_, ticks)) = &mut self.toast {
+            *ticks -= 1;
+            if *ticks == 0 {
+                self.toast = None;
+            }
+        }
+
+        if let Some(held) = &mut self.shoot_held {
+            *held = held.saturating_add(1);
+            if *held == EJECT_HOLD_TICKS
+                && self.screen == Screen::Reload
+                && self.secondary_gun.eject_all()
+            {
+                self.rumble = Some(Rumble::Start((EJECT_RUMBLE_TICKS, EJECT_RUMBLE_DIVIDER)));
+            }
+        }
+
+        if let Some(held) = &mut self.reload_held {
+            *held = held.saturating_add(1);
+            if *held == SWITCH_HOLD_TICKS {
+                // otherwise it stays held until released, shoot can still pause the game
+                let handled = match self.screen {
+                    Screen::Normal if self.primary_gun.is_some() => {
+                        self.switch_gun();
+                        true
+                    }
+                    Screen::Start => {
+                        self.screen = Screen::Records;
+                        self.page = 0;
+                        true
+                    }
+                    Screen::Challenge => {
+                        self.screen = Screen::Start;
+                        true
+                    }
+                    _ => false,
+                };
+                if handled {
+                    self.reload_held = None;
+                }
+            }
+        }
+
+        if let Some(primary) = &mut self.primary_gun {
+            primary.weapon_mut().tick();
+        }
+        self.secondary_gun.tick();
+
+        // keep a fresh seed around for the next game
+        if self.screen == Screen::Start {
+            self.seed = random.next_u32();
+        }
+
         if let Screen::Normal | Screen::Reload = self.screen {
+            self.stats.add_tick(self.score);
+            if self
+                .mode
+                .time_limit()
+                .is_some_and(|limit| self.stats.ticks >= limit)
+            {
+                self.game_over();
+                return;
+            }
+            self.tick_bullets();
+
+            for kind in self.pickups.tick(self.y).into_iter().flatten() {
+                self.collect(kind);
+            }
+
             if let Some(next_shot) = self.next_shot {
                 let next_shot = next_shot.saturating_sub(1);
                 if next_shot == 0 {
@@ -161,8 +735,27 @@ impl Game {
                 }
             }
 
-            if self.lawn.tick(self.score, random) {
-                self.screen = Screen::Wasted;
+            self.power_ups.tick();
+            self.combo.tick();
+            // frozen opponents don't move at all, in bullet time every other tick
+            let stand_still = self.power_ups.is_active(PowerUp::Freeze)
+                || (self.power_ups.is_active(PowerUp::BulletTime) && self.frame.is_multiple_of(2));
+            let hit = if stand_still {
+                None
+            } else {
+                self.lawn.tick(self.score, self.y, &mut self.random)
+            };
+            if let Some(cause) = hit {
+                self.hurt(cause);
+            }
+
+            if let Some(director) = &mut self.director {
+                if director
+                    .tick(&self.stats, self.lawn.closest(), hit.is_some())
+                    .is_some()
+                {
+                    self.lawn.set_adjustment(director.adjustment());
+                }
             }
         }
     }
@@ -175,14 +768,48 @@ impl Game {
     }
 
     fn schedule_next_shot(&mut self) {
-        match self.gun() {
-            Gun::Revolver(_) => (),
-            Gun::Scorpio(_) => {
-                self.next_shot = Some(Scorpio::FIRE_RATE);
-            }
+        let fire_rate = self.gun().fire_rate();
+        if self.power_ups.is_active(PowerUp::RapidFire) {
+            // every gun turns automatic, automatic ones fire twice as fast
+            let fire_rate =
+                fire_rate.map_or(powerups::RAPID_FIRE_RATE, |rate| u8::max(rate / 2, 1));
+            self.next_shot = Some(fire_rate);
+        } else if let Some(fire_rate) = fire_rate {
+            self.next_shot = Some(fire_rate);
         }
     }
 
+    fn start(&mut self) {
+        // everyone gets the same lawn on the same challenge date, by the same rules
+        let challenge = self.mode == GameMode::Challenge;
+        let seed = if challenge {
+            self.challenge.seed()
+        } else {
+            self.seed
+        };
+        let secondary_gun = if self.realism && !challenge {
+            Revolver::new().with_realism(seed)
+        } else {
+            Revolver::new()
+        };
+        *self = Game {
+            screen: Screen::Normal,
+            secondary_gun: secondary_gun.with_reserve_limit(self.mode.reserve_limit()),
+            // don't tie spawns and drops to misfires
+            random: XorShift::new(seed.rotate_left(8)),
+            pickups: Pickups::new(seed.rotate_left(16), self.mode),
+            realism: self.realism,
+            adaptive: self.adaptive,
+            director: (self.adaptive && !challenge).then(Director::default),
+            mode: self.mode,
+            challenge: self.challenge,
+            seed,
+            lifetime: self.lifetime,
+            ..Default::default()
+        };
+        self.shoot();
+    }
+
     pub fn action(&mut self, action: &Action) {
         // debouncing
         match action {
@@ -199,17 +826,70 @@ impl Game {
             _ => (),
         }
 
+        // track how long shoot is held down
+        match action {
+            Action::Press(Button::Shoot) => {
+                self.shoot_held = Some(0);
+                self.burst_kills = 0;
+            }
+            Action::Release(Button::Shoot) => self.shoot_held = None,
+            _ => (),
+        }
+
+        // a short press of reload opens and closes the reload screen, a long one switches
+        // weapons, on the start screen it toggles realism and opens the records, while
+        // entering a challenge it moves to the next field and goes back
+        let reload_tapped = match action {
+            Action::Press(Button::ReloadToggle)
+                if matches!(
+                    self.screen,
+                    Screen::Normal | Screen::Reload | Screen::Start | Screen::Challenge
+                ) =>
+            {
+                self.reload_held = Some(0);
+                false
+            }
+            Action::Release(Button::ReloadToggle) => self.reload_held.take().is_some(),
+            _ => false,
+        };
+
         // process action
         match (self.screen, action) {
             // start screen
             (Screen::Start, Action::Press(Button::Shoot)) => {
-                *self = Game {
-                    screen: Screen::Normal,
-                    ..Default::default()
-                };
-                self.shoot();
+                if self.mode == GameMode::Challenge {
+                    self.screen = Screen::Challenge;
+                    self.challenge_field = Field::Year;
+                } else {
+                    self.start();
+                }
+            }
+            (Screen::Start, Action::Release(Button::ReloadToggle)) => {
+                if reload_tapped {
+                    self.realism = !self.realism;
+                }
+            }
+            (Screen::Start, Action::Rotate(Direction::Clockwise)) => {
+                self.mode = self.mode.next();
+            }
+            (Screen::Start, Action::Rotate(Direction::CounterClock)) => {
+                self.mode = self.mode.previous();
             }
             (Screen::Start, _) => {}
+            // challenge date
+            (Screen::Challenge, Action::Rotate(Direction::Clockwise)) => {
+                self.challenge.adjust(self.challenge_field, true);
+            }
+            (Screen::Challenge, Action::Rotate(Direction::CounterClock)) => {
+                self.challenge.adjust(self.challenge_field, false);
+            }
+            (Screen::Challenge, Action::Release(Button::ReloadToggle)) => {
+                if reload_tapped {
+                    self.challenge_field = self.challenge_field.next();
+                }
+            }
+            (Screen::Challenge, Action::Press(Button::Shoot)) => self.start(),
+            (Screen::Challenge, _) => {}
             // default screen
             (Screen::Normal, Action::Rotate(Direction::Clockwise)) => {
                 if self.y + STEP_Y <= gfx::FERRIS_MAX_Y {
@@ -217,17 +897,22 @@ impl Game {
                 }
             }
             (Screen::Normal, Action::Rotate(Direction::CounterClock)) => {
-                self.y = self.y.saturating_sub(STEP_Y);
+                self.y = u8::max(self.y.saturating_sub(STEP_Y), gfx::FERRIS_MIN_Y);
             }
-            (Screen::Normal, Action::Press(Button::ReloadToggle)) => {
+            (Screen::Normal, Action::Release(Button::ReloadToggle)) => {
                 // only the revolver can be reloaded
-                match self.gun() {
-                    Gun::Revolver(_) => {
-                        self.screen = Screen::Reload;
-                    }
-                    Gun::Scorpio(_) => (),
+                if reload_tapped && self.gun().is_reloadable() {
+                    self.stats.reloads += 1;
+                    self.screen = Screen::Reload;
                 }
             }
+            // handled on release, holding it down switches weapons
+            (Screen::Normal, Action::Press(Button::ReloadToggle)) => (),
+            // pressing shoot while holding reload pauses the game
+            (Screen::Normal, Action::Press(Button::Shoot)) if self.reload_held.is_some() => {
+                self.reload_held = None;
+                self.pause();
+            }
             (Screen::Normal, Action::Press(Button::Shoot)) => {
                 self.shoot();
                 self.schedule_next_shot();
@@ -239,17 +924,51 @@ impl Game {
             (Screen::Reload, Action::Rotate(Direction::CounterClock)) => {
                 self.secondary_gun.drum_counterclock();
             }
+            (Screen::Reload, Action::Press(Button::Shoot)) if self.reload_held.is_some() => {
+                self.reload_held = None;
+                self.pause();
+            }
             (Screen::Reload, Action::Press(Button::Shoot)) => {
                 self.secondary_gun.reload();
             }
-            (Screen::Reload, Action::Press(Button::ReloadToggle)) => {
+            // handled on release, like opening it
+            (Screen::Reload, Action::Press(Button::ReloadToggle)) => (),
+            (Screen::Reload, Action::Release(Button::ReloadToggle)) if reload_tapped => {
                 self.screen = Screen::Normal;
+                let loaded = self.secondary_gun.ammo() == self.secondary_gun.capacity();
+                let close = self
+                    .lawn
+                    .closest()
+                    .is_some_and(|distance| distance <= opps::DANGER_DISTANCE);
+                if loaded && close {
+                    self.unlock(Achievement::CloseCall);
+                }
+            }
+            // pause screen
+            (Screen::Paused, Action::Rotate(_)) => self.quit_selected = !self.quit_selected,
+            (Screen::Paused, Action::Press(Button::Shoot)) => {
+                if self.quit_selected {
+                    self.game_over();
+                } else {
+                    self.screen = self.paused_from;
+                }
             }
+            (Screen::Paused, Action::Press(_)) => {}
             // game over screen
+            (Screen::Wasted | Screen::Records, Action::Rotate(Direction::Clockwise)) => {
+                self.page = u8::min(self.page + 1, self.pages() - 1);
+            }
+            (Screen::Wasted | Screen::Records, Action::Rotate(Direction::CounterClock)) => {
+                self.page = self.page.saturating_sub(1);
+            }
             (Screen::Wasted, Action::Press(Button::Shoot)) => {
                 self.screen = Screen::Start;
             }
             (Screen::Wasted, _) => {}
+            // records screen
+            (Screen::Records, Action::Press(_)) => {
+                self.screen = Screen::Start;
+            }
             // misc
             (_, Action::Release(Button::Shoot)) => {
                 self.next_shot = None;
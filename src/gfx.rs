@@ -19,12 +19,31 @@ pub const BLACK_LINE: PrimitiveStyle<BinaryColor> =
 pub const WHITE_FILL: PrimitiveStyle<BinaryColor> = PrimitiveStyle::with_fill(BinaryColor::On);
 pub const BLACK_FILL: PrimitiveStyle<BinaryColor> = PrimitiveStyle::with_fill(BinaryColor::Off);
 pub const CHAR_WIDTH: usize = 4;
+pub const LIFE_SPACING: i32 = 4;
+
+// the top rows are reserved for the HUD, nothing else is drawn there
+pub const HUD_HEIGHT: u8 = 7;
+pub const HUD_PIP_SPACING: i32 = 4;
+pub const HUD_BAR_WIDTH: u32 = 20;
+// active power-ups and the combo go right after the weapon, wave and boss info in the middle
+pub const HUD_POWER_UPS_X: i32 = 46;
+pub const HUD_POWER_UP_SPACING: i32 = 7;
+pub const HUD_COMBO_X: i32 = 67;
+pub const HUD_CENTER_X: i32 = 76;
+// the whole lawn squeezed into the HUD strip of the reload screen
+pub const MINI_LAWN_WIDTH: i32 = 48;
 
 pub const FERRIS_HEIGHT: u8 = 30;
+// ferris with the revolver, the other guns only have a longer barrel
+pub const FERRIS_WIDTH: u8 = 52;
 pub const FERRIS_OFFSET: u8 = 62; // the largest possible ferris
+pub const FERRIS_MIN_Y: u8 = HUD_HEIGHT;
 pub const FERRIS_MAX_Y: u8 = SCREEN_HEIGHT - FERRIS_HEIGHT;
 
+pub const OPPONENT_WIDTH: u8 = 29;
 pub const OPPONENT_HEIGHT: u8 = 21;
+pub const TRACER_LENGTH: i32 = 6;
+pub const PROJECTILE_LENGTH: i32 = 3;
 
 // Ferris: 53x30
 pub const FERRIS_REVOLVER: ImageRaw<BinaryColor> =
@@ -32,6 +51,12 @@ pub const FERRIS_REVOLVER: ImageRaw<BinaryColor> =
 // Ferris: 62x30
 pub const FERRIS_SCORPIO: ImageRaw<BinaryColor> =
     ImageRaw::new(include_bytes!("../data/ferris-scorpio.raw"), 61);
+// Ferris: 62x30
+pub const FERRIS_SHOTGUN: ImageRaw<BinaryColor> =
+    ImageRaw::new(include_bytes!("../data/ferris-shotgun.raw"), 61);
+// Ferris: 62x30
+pub const FERRIS_SNIPER: ImageRaw<BinaryColor> =
+    ImageRaw::new(include_bytes!("../data/ferris-sniper.raw"), 61);
 // Opponent: 30x22
 pub const OPPONENT: ImageRaw<BinaryColor> =
     ImageRaw::new(include_bytes!("../data/opponent.raw"), 29);
@@ -42,6 +67,11 @@ pub const fn text_align_right(text: &str, total: u8) -> i32 {
     (total as usize - (text.len() * CHAR_WIDTH)) as i32
 }
 
+pub const fn text_align_center(text: &str, total: u8) -> i32 {
+    text_align_right(text, total) / 2
+}
+
+pub const DRUM_CENTER: Point = Point::new(94, 32);
 pub const CHAMBER_POSITIONS: &[Point] = &[
     Point::new(70, 14),
     Point::new(70, 32),
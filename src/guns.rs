@@ -1,25 +1,141 @@
 use crate::gfx;
+use crate::rng::XorShift;
 use core::iter::Chain;
+use core::ops::RangeInclusive;
 use core::slice;
+use embedded_graphics::{image::ImageRaw, pixelcolor::BinaryColor};
+use rand_core::RngCore;
 
 pub const REVOLVER_OFFSET: u8 = 7;
 pub const SCORPIO_OFFSET: u8 = 8;
+pub const SHOTGUN_OFFSET: u8 = 8;
+pub const SNIPER_OFFSET: u8 = 7;
+
+// the most rays a single shot can consist of
+pub const MAX_RAYS: usize = 5;
 
 pub const SHOT_RUMBLE_TICKS: u8 = 2;
 pub const SHOT_RUMBLE_DIVIDER: u16 = 2;
 
-pub const MAX_GUARANTEED_REACH: u8 = gfx::min(REVOLVER_OFFSET, SCORPIO_OFFSET);
+pub const MAX_GUARANTEED_REACH: u8 = gfx::min(
+    gfx::min(REVOLVER_OFFSET, SCORPIO_OFFSET),
+    gfx::min(SHOTGUN_OFFSET, SNIPER_OFFSET),
+);
 
-pub enum Gun<'a> {
-    Revolver(&'a mut Revolver),
-    Scorpio(&'a mut Scorpio),
+/// Every weapon there is, for keeping track of things per weapon
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    Revolver,
+    Scorpio,
+    Shotgun,
+    Sniper,
 }
 
-impl<'a> Gun<'a> {
-    pub fn shoot(&mut self) -> Option<(bool, u8)> {
+impl Kind {
+    pub const ALL: [Kind; 4] = [Kind::Revolver, Kind::Scorpio, Kind::Shotgun, Kind::Sniper];
+
+    pub fn name(&self) -> &'static str {
         match self {
-            Gun::Revolver(gun) => Some(gun.shoot()),
-            Gun::Scorpio(gun) => gun.shoot(),
+            Kind::Revolver => "REV",
+            Kind::Scorpio => "SMG",
+            Kind::Shotgun => "SG",
+            Kind::Sniper => "SR",
+        }
+    }
+}
+
+pub trait Weapon {
+    /// Pull the trigger, returns if a shot was fired or `None` if the weapon is used up
+    fn shoot(&mut self) -> Option<bool>;
+
+    /// Where the barrel is, relative to the top of ferris
+    fn offset(&self) -> u8;
+
+    /// Offsets of every ray of a single shot, relative to the barrel
+    fn rays(&self) -> &'static [i8] {
+        &[0]
+    }
+
+    /// How many opponents in a row a single ray can hit
+    fn penetration(&self) -> u8 {
+        1
+    }
+
+    /// Pixels per tick the bullets travel, `None` for hitscan
+    fn bullet_speed(&self) -> Option<u8> {
+        None
+    }
+
+    /// Ticks between shots while the trigger is held down, `None` for single shots
+    fn fire_rate(&self) -> Option<u8> {
+        None
+    }
+
+    /// Rounds that are ready to be fired
+    fn ammo(&self) -> u8;
+
+    /// Rounds the weapon holds when fully loaded
+    fn capacity(&self) -> u8;
+
+    fn kind(&self) -> Kind;
+
+    /// Short label for the HUD
+    fn name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    /// Ferris holding this weapon
+    fn sprite(&self) -> &'static ImageRaw<'static, BinaryColor>;
+
+    /// If the reload screen can be opened while holding this weapon
+    fn is_reloadable(&self) -> bool {
+        false
+    }
+
+    /// Rumble duration and divider for every shot fired
+    fn rumble(&self) -> (u8, u16) {
+        (SHOT_RUMBLE_TICKS, SHOT_RUMBLE_DIVIDER)
+    }
+
+    /// Called on every game tick
+    fn tick(&mut self) {}
+}
+
+/// The slot for the weapon that's picked up during the game
+///
+/// New weapons only need a variant here, everything else goes through `Weapon`.
+pub enum Primary {
+    Scorpio(Scorpio),
+    Shotgun(Shotgun),
+    Sniper(Sniper),
+}
+
+impl Primary {
+    pub fn scorpio() -> Self {
+        Primary::Scorpio(Scorpio::new())
+    }
+
+    pub fn shotgun() -> Self {
+        Primary::Shotgun(Shotgun::new())
+    }
+
+    pub fn sniper() -> Self {
+        Primary::Sniper(Sniper::new())
+    }
+
+    pub fn weapon(&self) -> &dyn Weapon {
+        match self {
+            Primary::Scorpio(gun) => gun,
+            Primary::Shotgun(gun) => gun,
+            Primary::Sniper(gun) => gun,
+        }
+    }
+
+    pub fn weapon_mut(&mut self) -> &mut dyn Weapon {
+        match self {
+            Primary::Scorpio(gun) => gun,
+            Primary::Shotgun(gun) => gun,
+            Primary::Sniper(gun) => gun,
         }
     }
 }
@@ -27,9 +143,37 @@ impl<'a> Gun<'a> {
 pub struct Revolver {
     chambers: [Chamber; 6],
     drum_cursor: u8,
+    reserve: u8,
+    max_reserve: u8,
+    realism: Option<Realism>,
+}
+
+/// Manual handling for the revolver: the hammer needs to be cocked before
+/// every shot and cartridges can misfire
+struct Realism {
+    cocked: bool,
+    misfires: XorShift,
+}
+
+impl Realism {
+    // chance of a cartridge misfiring, out of 256
+    const MISFIRE_CHANCE: u32 = 8;
+
+    fn misfire(&mut self) -> bool {
+        self.misfires.next_u32() % 256 < Self::MISFIRE_CHANCE
+    }
+}
+
+impl Default for Revolver {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Revolver {
+    pub const START_RESERVE: u8 = 24;
+    pub const MAX_RESERVE: u8 = 36;
+
     pub fn new() -> Self {
         Self {
             chambers: [
@@ -41,9 +185,41 @@ impl Revolver {
                 Chamber::Loaded,
             ],
             drum_cursor: 0,
+            reserve: Self::START_RESERVE,
+            max_reserve: Self::MAX_RESERVE,
+            realism: None,
         }
     }
 
+    /// Enable realism mode, misfires are derived from `seed`
+    pub fn with_realism(mut self, seed: u32) -> Self {
+        self.realism = Some(Realism {
+            cocked: false,
+            misfires: XorShift::new(seed),
+        });
+        self
+    }
+
+    /// Never hold more than `limit` cartridges in the reserve
+    pub fn with_reserve_limit(mut self, limit: u8) -> Self {
+        self.max_reserve = limit;
+        self.reserve = u8::min(self.reserve, limit);
+        self
+    }
+
+    pub fn is_cocked(&self) -> bool {
+        self.realism.as_ref().is_some_and(|realism| realism.cocked)
+    }
+
+    /// Cartridges that are left for reloading
+    pub fn reserve(&self) -> u8 {
+        self.reserve
+    }
+
+    pub fn add_reserve(&mut self, cartridges: u8) {
+        self.reserve = u8::min(self.reserve.saturating_add(cartridges), self.max_reserve);
+    }
+
     /// Create an iterator that walks over all chambers, in order, starting at the cursor
     pub fn chambers(&self) -> Chain<slice::Iter<'_, Chamber>, slice::Iter<'_, Chamber>> {
         self.chambers[(self.drum_cursor as usize)..]
@@ -65,53 +241,366 @@ impl Revolver {
         self.drum_cursor %= self.chambers.len() as u8;
     }
 
-    pub fn shoot(&mut self) -> (bool, u8) {
-        self.drum_clockwise();
+    pub fn shoot(&mut self) -> bool {
+        let misfire = match &mut self.realism {
+            // the first press cocks the hammer and rotates the drum
+            Some(realism) if !realism.cocked => {
+                realism.cocked = true;
+                self.drum_clockwise();
+                return false;
+            }
+            // the second press fires
+            Some(realism) => {
+                realism.cocked = false;
+                realism.misfire()
+            }
+            None => {
+                self.drum_clockwise();
+                false
+            }
+        };
+
         match self.chambers().next() {
             Some(Chamber::Empty) => (),
+            Some(Chamber::Loaded) if misfire => {
+                self.set_chamber(Chamber::Dud);
+            }
             Some(Chamber::Loaded) => {
                 self.set_chamber(Chamber::Shot);
-                return (true, REVOLVER_OFFSET);
+                return true;
             }
             Some(Chamber::Shot) => (),
+            Some(Chamber::Dud) => (),
             None => (),
         }
-        (false, REVOLVER_OFFSET)
+        false
     }
 
     pub fn reload(&mut self) {
         match self.chambers().next() {
-            Some(Chamber::Empty) => {
+            // loading takes a cartridge from the reserve
+            Some(Chamber::Empty) if self.reserve > 0 => {
+                self.reserve -= 1;
                 self.set_chamber(Chamber::Loaded);
             }
+            Some(Chamber::Empty) => (),
             Some(Chamber::Loaded) => (),
-            Some(Chamber::Shot) => {
+            Some(Chamber::Shot | Chamber::Dud) => {
                 self.set_chamber(Chamber::Empty);
             }
             None => (),
         }
     }
+
+    /// Eject all spent casings at once, returns true if there were any
+    pub fn eject_all(&mut self) -> bool {
+        let mut ejected = false;
+        for chamber in &mut self.chambers {
+            if let Chamber::Shot = chamber {
+                *chamber = Chamber::Empty;
+                ejected = true;
+            }
+        }
+        ejected
+    }
+}
+
+impl Weapon for Revolver {
+    fn shoot(&mut self) -> Option<bool> {
+        Some(Revolver::shoot(self))
+    }
+
+    fn offset(&self) -> u8 {
+        REVOLVER_OFFSET
+    }
+
+    fn bullet_speed(&self) -> Option<u8> {
+        Some(8)
+    }
+
+    fn ammo(&self) -> u8 {
+        self.chambers
+            .iter()
+            .filter(|chamber| matches!(chamber, Chamber::Loaded))
+            .count() as u8
+    }
+
+    fn capacity(&self) -> u8 {
+        self.chambers.len() as u8
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Revolver
+    }
+
+    fn sprite(&self) -> &'static ImageRaw<'static, BinaryColor> {
+        &gfx::FERRIS_REVOLVER
+    }
+
+    fn is_reloadable(&self) -> bool {
+        true
+    }
 }
 
 pub struct Scorpio {
     rounds: u8,
 }
 
+impl Default for Scorpio {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Scorpio {
     pub const FIRE_RATE: u8 = 3;
+    pub const ROUNDS: u8 = 20;
 
     pub fn new() -> Self {
-        Self { rounds: 20 }
+        Self {
+            rounds: Self::ROUNDS,
+        }
     }
+}
 
-    pub fn shoot(&mut self) -> Option<(bool, u8)> {
+impl Weapon for Scorpio {
+    fn shoot(&mut self) -> Option<bool> {
         if self.rounds > 0 {
             self.rounds -= 1;
-            Some((true, SCORPIO_OFFSET))
+            Some(true)
         } else {
             None
         }
     }
+
+    fn offset(&self) -> u8 {
+        SCORPIO_OFFSET
+    }
+
+    fn fire_rate(&self) -> Option<u8> {
+        Some(Self::FIRE_RATE)
+    }
+
+    fn bullet_speed(&self) -> Option<u8> {
+        Some(6)
+    }
+
+    fn ammo(&self) -> u8 {
+        self.rounds
+    }
+
+    fn capacity(&self) -> u8 {
+        Self::ROUNDS
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Scorpio
+    }
+
+    fn sprite(&self) -> &'static ImageRaw<'static, BinaryColor> {
+        &gfx::FERRIS_SCORPIO
+    }
+}
+
+pub struct Shotgun {
+    shells: u8,
+    pump: u8,
+}
+
+impl Default for Shotgun {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shotgun {
+    // ticks it takes to pump the next shell in
+    pub const PUMP_DELAY: u8 = 6;
+    pub const SPREAD: [i8; MAX_RAYS] = [-8, -4, 0, 4, 8];
+    pub const SHELLS: u8 = 8;
+
+    pub fn new() -> Self {
+        Self {
+            shells: Self::SHELLS,
+            pump: 0,
+        }
+    }
+}
+
+impl Weapon for Shotgun {
+    fn shoot(&mut self) -> Option<bool> {
+        if self.shells == 0 {
+            return None;
+        }
+        if self.pump > 0 {
+            return Some(false);
+        }
+        self.shells -= 1;
+        self.pump = Self::PUMP_DELAY;
+        Some(true)
+    }
+
+    fn offset(&self) -> u8 {
+        SHOTGUN_OFFSET
+    }
+
+    fn rays(&self) -> &'static [i8] {
+        &Self::SPREAD
+    }
+
+    fn ammo(&self) -> u8 {
+        self.shells
+    }
+
+    fn capacity(&self) -> u8 {
+        Self::SHELLS
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Shotgun
+    }
+
+    fn sprite(&self) -> &'static ImageRaw<'static, BinaryColor> {
+        &gfx::FERRIS_SHOTGUN
+    }
+
+    fn rumble(&self) -> (u8, u16) {
+        (SHOT_RUMBLE_TICKS + 1, 1)
+    }
+
+    fn tick(&mut self) {
+        self.pump = self.pump.saturating_sub(1);
+    }
+}
+
+pub struct Sniper {
+    rounds: u8,
+    bolt: u8,
+}
+
+impl Default for Sniper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sniper {
+    // ticks it takes to cycle the bolt
+    pub const BOLT_DELAY: u8 = 10;
+    pub const PENETRATION: u8 = 4;
+    pub const ROUNDS: u8 = 5;
+
+    pub fn new() -> Self {
+        Self {
+            rounds: Self::ROUNDS,
+            bolt: 0,
+        }
+    }
+}
+
+impl Weapon for Sniper {
+    fn shoot(&mut self) -> Option<bool> {
+        if self.rounds == 0 {
+            return None;
+        }
+        if self.bolt > 0 {
+            return Some(false);
+        }
+        self.rounds -= 1;
+        self.bolt = Self::BOLT_DELAY;
+        Some(true)
+    }
+
+    fn offset(&self) -> u8 {
+        SNIPER_OFFSET
+    }
+
+    fn penetration(&self) -> u8 {
+        Self::PENETRATION
+    }
+
+    fn ammo(&self) -> u8 {
+        self.rounds
+    }
+
+    fn capacity(&self) -> u8 {
+        Self::ROUNDS
+    }
+
+    fn kind(&self) -> Kind {
+        Kind::Sniper
+    }
+
+    fn sprite(&self) -> &'static ImageRaw<'static, BinaryColor> {
+        &gfx::FERRIS_SNIPER
+    }
+
+    fn rumble(&self) -> (u8, u16) {
+        (SHOT_RUMBLE_TICKS + 1, 1)
+    }
+
+    fn tick(&mut self) {
+        self.bolt = self.bolt.saturating_sub(1);
+    }
+}
+
+/// A bullet in flight, for weapons that aren't hitscan
+pub struct Bullet {
+    x: u8,
+    y: u8,
+    speed: u8,
+    penetration: u8,
+    struck: bool,
+    kind: Kind,
+}
+
+impl Bullet {
+    pub fn new(x: u8, y: u8, speed: u8, penetration: u8, kind: Kind) -> Self {
+        Self {
+            x,
+            y,
+            speed,
+            penetration,
+            struck: false,
+            kind,
+        }
+    }
+
+    /// The weapon that fired this bullet
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    pub fn penetration(&self) -> u8 {
+        self.penetration
+    }
+
+    /// If the bullet hit anything at all on its way
+    pub fn has_struck(&self) -> bool {
+        self.struck
+    }
+
+    /// Move the bullet, returns the range it travelled through
+    pub fn tick(&mut self) -> RangeInclusive<u8> {
+        let from = self.x;
+        self.x = self.x.saturating_add(self.speed);
+        from..=self.x
+    }
+
+    /// Use up penetration for opponents that got hit, returns true if the bullet is spent
+    pub fn absorb(&mut self, hits: u32) -> bool {
+        self.penetration = self.penetration.saturating_sub(hits as u8);
+        self.struck |= hits > 0;
+        self.penetration == 0 || self.x >= gfx::SCREEN_WIDTH
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -119,4 +608,119 @@ pub enum Chamber {
     Empty,
     Loaded,
     Shot,
+    /// A cartridge that misfired, it needs to be ejected by hand
+    Dud,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modes::GameMode;
+
+    #[test]
+    fn loading_takes_from_the_reserve() {
+        let mut revolver = Revolver::new();
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Empty)));
+
+        revolver.reload();
+        assert_eq!(revolver.ammo(), 6);
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE - 1);
+
+        // a loaded chamber stays as it is
+        revolver.reload();
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE - 1);
+    }
+
+    #[test]
+    fn loading_with_an_empty_reserve() {
+        let mut revolver = Revolver::new();
+        revolver.reserve = 0;
+
+        revolver.reload();
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Empty)));
+        assert_eq!(revolver.ammo(), 5);
+        assert_eq!(revolver.reserve(), 0);
+    }
+
+    #[test]
+    fn shots_are_ejected_and_reloaded_one_by_one() {
+        let mut revolver = Revolver::new();
+        assert!(revolver.shoot());
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Shot)));
+
+        // the first press ejects the casing, the second loads a new cartridge
+        revolver.reload();
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Empty)));
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE);
+        revolver.reload();
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Loaded)));
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE - 1);
+    }
+
+    #[test]
+    fn reserve_is_capped() {
+        let mut revolver = Revolver::new();
+        revolver.add_reserve(u8::MAX);
+        assert_eq!(revolver.reserve(), Revolver::MAX_RESERVE);
+    }
+
+    #[test]
+    fn hardcore_reserve_is_capped() {
+        let limit = GameMode::Hardcore.reserve_limit();
+        let mut revolver = Revolver::new().with_reserve_limit(limit);
+        assert_eq!(revolver.reserve(), limit);
+
+        revolver.add_reserve(6);
+        assert_eq!(revolver.reserve(), limit);
+
+        revolver.reserve = 0;
+        revolver.add_reserve(6);
+        assert_eq!(revolver.reserve(), 6);
+    }
+
+    #[test]
+    fn live_rounds_are_never_refunded() {
+        let mut revolver = Revolver::new();
+        revolver.drum_clockwise();
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Loaded)));
+
+        revolver.reload();
+        assert!(!revolver.eject_all());
+        assert_eq!(revolver.ammo(), 5);
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE);
+    }
+
+    #[test]
+    fn eject_all_leaves_duds() {
+        let mut revolver = Revolver::new();
+        revolver.chambers = [
+            Chamber::Shot,
+            Chamber::Dud,
+            Chamber::Loaded,
+            Chamber::Empty,
+            Chamber::Shot,
+            Chamber::Shot,
+        ];
+
+        assert!(revolver.eject_all());
+        assert!(matches!(
+            revolver.chambers,
+            [
+                Chamber::Empty,
+                Chamber::Dud,
+                Chamber::Loaded,
+                Chamber::Empty,
+                Chamber::Empty,
+                Chamber::Empty,
+            ]
+        ));
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE);
+        assert!(!revolver.eject_all());
+
+        // duds have to be ejected by hand
+        revolver.drum_clockwise();
+        revolver.reload();
+        assert!(matches!(revolver.chambers().next(), Some(Chamber::Empty)));
+        assert_eq!(revolver.reserve(), Revolver::START_RESERVE);
+    }
 }
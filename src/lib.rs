@@ -0,0 +1,19 @@
+#![no_std]
+
+// everything but the hardware, so it can also run on the host
+pub mod ai;
+pub mod attract;
+pub mod boss;
+pub mod challenge;
+pub mod director;
+pub mod game;
+pub mod gfx;
+pub mod guns;
+pub mod lifetime;
+pub mod modes;
+pub mod opps;
+pub mod pickups;
+pub mod powerups;
+pub mod rng;
+pub mod score;
+pub mod stats;
@@ -0,0 +1,168 @@
+use crate::modes::GameMode;
+use crate::stats::RunStats;
+
+// bump the version whenever fields are appended below, older records are read as far
+// as they go and the new fields start out empty
+const MAGIC: [u8; 4] = *b"GDP!";
+const VERSION: u8 = 2;
+pub const RECORD_SIZE: usize = 256;
+// games with fewer shots don't count for the best accuracy
+const MIN_ACCURACY_SHOTS: u32 = 10;
+const VETERAN_GAMES: u32 = 10;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Achievement {
+    /// Finish a reload with all six chambers loaded while an opponent is close
+    CloseCall,
+    /// Kill 3 opponents with a single scorpio burst
+    Burst,
+    /// Kill 3 opponents with a single shot
+    Skewer,
+    BossSlayer,
+    /// Reach the highest combo multiplier
+    MaxCombo,
+    /// Score 50 points without losing a life
+    Untouchable,
+    /// Play 10 games
+    Veteran,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 7] = [
+        Achievement::CloseCall,
+        Achievement::Burst,
+        Achievement::Skewer,
+        Achievement::BossSlayer,
+        Achievement::MaxCombo,
+        Achievement::Untouchable,
+        Achievement::Veteran,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Achievement::CloseCall => "CLOSE CALL",
+            Achievement::Burst => "SCORPIO BURST",
+            Achievement::Skewer => "SKEWER",
+            Achievement::BossSlayer => "BOSS SLAYER",
+            Achievement::MaxCombo => "MAX COMBO",
+            Achievement::Untouchable => "UNTOUCHABLE",
+            Achievement::Veteran => "VETERAN",
+        }
+    }
+
+    fn bit(&self) -> u32 {
+        1 << (*self as u32)
+    }
+}
+
+/// Counters across all games ever played, persisted in flash
+#[derive(Default, Clone, Copy)]
+pub struct Lifetime {
+    pub games: u32,
+    pub kills: u32,
+    pub shots: u32,
+    /// In percent
+    pub best_accuracy: u32,
+    /// The most ticks survived in a single game
+    pub longest: u32,
+    achievements: u32,
+    /// The best result in every ranked mode, 0 if there is none yet
+    bests: [u32; GameMode::RANKED.len()],
+}
+
+impl Lifetime {
+    /// Add a finished game to the counters, `result` is what the mode is ranked by
+    pub fn record(&mut self, mode: GameMode, run: &RunStats, result: Option<u32>) {
+        // the modes that aren't ranked come last
+        if let (Some(result), Some(best)) = (result, self.bests.get_mut(mode as usize)) {
+            if *best == 0 || mode.is_better(result, *best) {
+                *best = result;
+            }
+        }
+        self.games = self.games.saturating_add(1);
+        self.kills = self.kills.saturating_add(run.kills);
+        self.shots = self.shots.saturating_add(run.shots);
+        if run.shots >= MIN_ACCURACY_SHOTS {
+            self.best_accuracy = u32::max(self.best_accuracy, run.accuracy());
+        }
+        self.longest = u32::max(self.longest, run.ticks);
+        if self.games >= VETERAN_GAMES {
+            self.unlock(Achievement::Veteran);
+        }
+    }
+
+    /// Returns true if the achievement wasn't unlocked before
+    pub fn unlock(&mut self, achievement: Achievement) -> bool {
+        let unlocked = self.is_unlocked(achievement);
+        self.achievements |= achievement.bit();
+        !unlocked
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.achievements & achievement.bit() != 0
+    }
+
+    pub fn best(&self, mode: GameMode) -> u32 {
+        self.bests.get(mode as usize).copied().unwrap_or(0)
+    }
+
+    pub fn to_bytes(self) -> [u8; RECORD_SIZE] {
+        let mut bytes = [0xff; RECORD_SIZE];
+        bytes[..4].copy_from_slice(&MAGIC);
+        bytes[4] = VERSION;
+        let fields = [
+            self.games,
+            self.kills,
+            self.shots,
+            self.best_accuracy,
+            self.longest,
+            self.achievements,
+        ]
+        .into_iter()
+        .chain(self.bests);
+        for (chunk, field) in bytes[8..].chunks_exact_mut(4).zip(fields) {
+            chunk.copy_from_slice(&field.to_le_bytes());
+        }
+        let checksum = checksum(&bytes[..RECORD_SIZE - 4]);
+        bytes[RECORD_SIZE - 4..].copy_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// `None` for erased flash, unknown versions and corrupted records
+    pub fn from_bytes(bytes: &[u8; RECORD_SIZE]) -> Option<Self> {
+        let version = bytes[4];
+        if bytes[..4] != MAGIC || !(1..=VERSION).contains(&version) {
+            return None;
+        }
+        let stored = u32::from_le_bytes(bytes[RECORD_SIZE - 4..].try_into().ok()?);
+        if stored != checksum(&bytes[..RECORD_SIZE - 4]) {
+            return None;
+        }
+
+        let mut fields = bytes[8..]
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+        let mut lifetime = Self {
+            games: fields.next()?,
+            kills: fields.next()?,
+            shots: fields.next()?,
+            best_accuracy: fields.next()?,
+            longest: fields.next()?,
+            achievements: fields.next()?,
+            bests: [0; GameMode::RANKED.len()],
+        };
+        // added in version 2
+        if version >= 2 {
+            for best in &mut lifetime.bests {
+                *best = fields.next()?;
+            }
+        }
+        Some(lifetime)
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0u32, |sum, &byte| sum.rotate_left(5) ^ byte as u32)
+}
@@ -1,25 +1,29 @@
 #![no_std]
 #![no_main]
 
-mod game;
-mod gfx;
-mod guns;
-mod opps;
+mod flash;
 
-use crate::game::{Action, Button, Direction, Game, Rumble, Screen};
-use crate::guns::{Chamber, Gun};
 use core::cell::RefCell;
+use core::fmt::Debug;
 use critical_section::Mutex;
 use defmt_rtt as _;
 use embedded_graphics::{
     image::Image,
+    pixelcolor::BinaryColor,
     prelude::*,
-    primitives::{Circle, Triangle},
+    primitives::{Circle, Line, Rectangle, Triangle},
     text::{Baseline, Text},
 };
 use embedded_hal::digital::v2::InputPin;
 use embedded_hal::PwmPin;
 use fugit::RateExtU32;
+use game_dont_panic::attract::Attract;
+use game_dont_panic::challenge::{self, Challenge};
+use game_dont_panic::game::{self, Action, Button, Direction, Game, Rumble, Screen};
+use game_dont_panic::guns::{self, Chamber};
+use game_dont_panic::lifetime::{Achievement, Lifetime};
+use game_dont_panic::modes::GameMode;
+use game_dont_panic::{gfx, opps, pickups};
 use panic_halt as _;
 use sh1106::{prelude::*, Builder};
 use usb_device::class_prelude::UsbBusAllocator;
@@ -63,6 +67,19 @@ fn rumble_off(channel: &mut PwmChannel) {
     channel.set_duty(0);
 }
 
+/// Draw a label with a number right after it
+fn draw_entry<D>(target: &mut D, label: &str, value: u32, position: Point) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    let mut buffer = itoa::Buffer::new();
+    let value = buffer.format(value);
+    let value_position = position + Point::new((label.len() * gfx::CHAR_WIDTH) as i32, 0);
+    Text::with_baseline(label, position, gfx::TEXT_STYLE, Baseline::Top).draw(target)?;
+    Text::with_baseline(value, value_position, gfx::TEXT_STYLE, Baseline::Top).draw(target)?;
+    Ok(())
+}
+
 #[entry]
 fn main() -> ! {
     let mut pac = pac::Peripherals::take().unwrap();
@@ -168,7 +185,9 @@ fn main() -> ! {
     }
 
     let mut last_action: Option<Action> = None;
-    let mut game = Game::new();
+    let lifetime = Lifetime::from_bytes(flash::read()).unwrap_or_default();
+    let mut game = Game::new().with_lifetime(lifetime);
+    let mut attract = Attract::default();
 
     let mut last_tick = timer.get_counter();
 
@@ -179,25 +198,34 @@ fn main() -> ! {
             buf.copy_from_slice(&*events.borrow());
 
             if let Some(action) = ACTION.borrow(cs).take() {
-                game.action(&action);
+                // any input ends the attract mode, without doing anything else
+                if !attract.wake() {
+                    game.action(&action);
+                }
                 last_action = Some(action);
             }
         });
 
         // execute game tick
-        let elapsed = last_tick.duration_since_epoch();
-        if elapsed > game::TICK_INTERVAL {
+        let now = timer.get_counter();
+        if now - last_tick >= game::TICK_INTERVAL {
             game.tick(&mut rosc);
-            last_tick = timer.get_counter();
+            attract.tick(game.screen(), &mut rosc);
+            last_tick = now;
+        }
+
+        // persist the lifetime counters after every game
+        if let Some(lifetime) = game.take_unsaved() {
+            flash::write(&lifetime.to_bytes());
         }
 
         // draw image
         display.clear();
-        match game.screen() {
-            Screen::Start => {
-                let im = Image::new(&gfx::FERRIS_REVOLVER, Point::new(0, game::START_Y as i32));
-                im.draw(&mut display).unwrap();
-
+        // the attract mode shows a demo game or the best scores instead of the start screen
+        match attract.demo() {
+            Some(demo) => draw(&mut display, demo),
+            None if attract.is_showing_scores() => {
+                draw_bests(&mut display, game.lifetime());
                 Text::with_baseline(
                     "Press shoot to start",
                     Point::new(25, 55),
@@ -207,88 +235,448 @@ fn main() -> ! {
                 .draw(&mut display)
                 .unwrap();
             }
-            Screen::Normal => {
-                // show ferris
-                let ferris = match game.gun() {
-                    Gun::Revolver(_) => &gfx::FERRIS_REVOLVER,
-                    Gun::Scorpio(_) => &gfx::FERRIS_SCORPIO,
-                };
-                let ferris = Image::new(ferris, Point::new(0, game.y() as i32));
-                ferris.draw(&mut display).unwrap();
+            None => draw(&mut display, &game),
+        }
+        display.flush().unwrap();
+
+        match (game.screen(), game.rumble) {
+            // always turn off rumble on these screens
+            (
+                Screen::Start
+                | Screen::Wasted
+                | Screen::Records
+                | Screen::Challenge
+                | Screen::Paused,
+                Some(_),
+            ) => {
+                rumble_off(&mut channel);
+                game.rumble = None;
+            }
+            // start rumble
+            (_, Some(Rumble::Start((duration, divider)))) => {
+                rumble_on(&mut channel, divider);
+                game.rumble = Some(Rumble::On(duration));
+            }
+            // stop rumble
+            (_, Some(Rumble::On(0))) => {
+                rumble_off(&mut channel);
+                game.rumble = None;
+            }
+            _ => (),
+        }
+
+        // test stuff
+        serial.write(&buf).ok();
+        /*
+        serial
+            .write(match last_action {
+                Some(Action::Rotate(Direction::Clockwise)) => b" rt cw",
+                Some(Action::Rotate(Direction::CounterClock)) => b" rt cc",
+                Some(Action::ReloadToggle) => b" rl",
+                Some(Action::Shoot) => b" shoot",
+                None => b" - none",
+            })
+            .ok();
+        */
+        serial.write(b"\n").ok();
 
-                for opp in game.lawn.opponents() {
-                    let opp =
-                        Image::new(&gfx::OPPONENT, Point::new(opp.x() as i32, opp.y() as i32));
-                    opp.draw(&mut display).unwrap();
+        if usb_dev.poll(&mut [&mut serial]) {
+            let mut buf = [0u8; 64];
+            // the host can pause the game, toggle adaptive difficulty for the next game,
+            // or push a challenge as a line like `daily 2026-10-18`
+            if let Ok(count) = serial.read(&mut buf[..]) {
+                let line = &buf[..count];
+                if line.trim_ascii() == b"pause" {
+                    game.pause();
+                } else if line.trim_ascii() == b"adaptive" {
+                    game.toggle_adaptive();
+                } else if let Some(challenge) = Challenge::parse(line) {
+                    game.set_challenge(challenge);
                 }
+            }
+        }
+    }
+}
 
-                // score
-                let mut score = itoa::Buffer::new();
-                let score = score.format(game.score());
+/// Everything on the screen, for the given state of the game
+fn draw<D>(display: &mut D, game: &Game)
+where
+    D: DrawTarget<Color = BinaryColor>,
+    D::Error: Debug,
+{
+    match game.screen() {
+        Screen::Start => {
+            let im = Image::new(&gfx::FERRIS_REVOLVER, Point::new(0, game::START_Y as i32));
+            im.draw(display).unwrap();
+
+            // rotate to pick the mode
+            let mode = game.mode().name();
+            let x = gfx::text_align_center(mode, gfx::SCREEN_WIDTH);
+            Text::with_baseline(mode, Point::new(x, 48), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
+            for (arrow, x) in [
+                ("<", x - 8),
+                (">", x + (mode.len() * gfx::CHAR_WIDTH) as i32 + 4),
+            ] {
+                Text::with_baseline(arrow, Point::new(x, 48), gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            Text::with_baseline(
+                "Press shoot to start",
+                Point::new(25, 55),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            Text::with_baseline(
+                "Hold reload for records",
+                Point::zero(),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            if game.is_realism() {
                 Text::with_baseline(
-                    score,
-                    Point::new(gfx::text_align_right(score, gfx::SCREEN_WIDTH), 0),
+                    "REALISM",
+                    Point::new(gfx::text_align_right("REALISM", gfx::SCREEN_WIDTH), 0),
                     gfx::TEXT_STYLE,
                     Baseline::Top,
                 )
-                .draw(&mut display)
+                .draw(display)
                 .unwrap();
             }
-            Screen::Reload => {
-                // show ferris
-                let im = Image::new(&gfx::FERRIS_REVOLVER, Point::new(0, game.y() as i32));
-                im.draw(&mut display).unwrap();
+            if game.is_adaptive() {
+                Text::with_baseline(
+                    "ADAPTIVE",
+                    Point::new(gfx::text_align_right("ADAPTIVE", gfx::SCREEN_WIDTH), 7),
+                    gfx::TEXT_STYLE,
+                    Baseline::Top,
+                )
+                .draw(display)
+                .unwrap();
+            }
+        }
+        Screen::Normal => {
+            // show ferris
+            let ferris = game.gun().sprite();
+            if game.is_ferris_visible() {
+                let ferris = Image::new(ferris, Point::new(0, game.y() as i32));
+                ferris.draw(display).unwrap();
+            }
+
+            for opp in game.lawn.opponents() {
+                let point = Point::new(opp.x() as i32, opp.y() as i32);
+                Image::new(&gfx::OPPONENT, point).draw(display).unwrap();
+
+                // gunners carry a barrel
+                if opp.is_gunner() {
+                    let barrel = point + Point::new(0, (gfx::OPPONENT_HEIGHT / 2) as i32);
+                    Line::new(barrel - Point::new(gfx::PROJECTILE_LENGTH, 0), barrel)
+                        .into_styled(gfx::WHITE_LINE)
+                        .draw(display)
+                        .unwrap();
+                }
+            }
+
+            // tracers
+            for bullet in game.bullets() {
+                let end = Point::new(bullet.x() as i32, bullet.y() as i32);
+                Line::new(end - Point::new(gfx::TRACER_LENGTH, 0), end)
+                    .into_styled(gfx::WHITE_LINE)
+                    .draw(display)
+                    .unwrap();
+            }
 
-                // show drum
-                Circle::new(Point::new(64, 2), 60)
+            for projectile in game.lawn.projectiles() {
+                let start = Point::new(projectile.x() as i32, projectile.y() as i32);
+                Line::new(start, start + Point::new(gfx::PROJECTILE_LENGTH, 0))
                     .into_styled(gfx::WHITE_LINE)
-                    .draw(&mut display)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            // pickups, blinking shortly before they disappear
+            for pickup in game.pickups().iter() {
+                if pickup.is_expiring() && !game.is_blink_on() {
+                    continue;
+                }
+                let size = pickups::PICKUP_SIZE as i32;
+                let top_left = Point::new(pickup.x() as i32, pickup.y() as i32 - size / 2);
+                Rectangle::new(top_left, Size::new(size as u32, size as u32))
+                    .into_styled(gfx::WHITE_LINE)
+                    .draw(display)
+                    .unwrap();
+                let label = top_left + Point::new(2, 1);
+                Text::with_baseline(pickup.kind().label(), label, gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            if let Some(boss) = game.lawn.boss() {
+                boss.draw(display).unwrap();
+            }
+
+            // wave indicator
+            if game.lawn.formation().is_some() {
+                let wave = game.lawn.waves() as u32 + 1;
+                let position = Point::new(gfx::HUD_CENTER_X, 0);
+                draw_entry(display, "W", wave, position).unwrap();
+            }
+
+            // HUD, active weapon and its rounds, a plus if another one is carried
+            let gun = game.gun();
+            Text::with_baseline(gun.name(), Point::zero(), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
+            let ammo_x = (gun.name().len() * gfx::CHAR_WIDTH) as i32 + 2;
+            let ammo_visible = gun.ammo() > 0 || game.is_blink_on();
+            let ammo_width = if gun.is_reloadable() {
+                // one pip per chamber, in drum order
+                for (i, chamber) in game.secondary_gun.chambers().enumerate() {
+                    let pip = Point::new(ammo_x + i as i32 * gfx::HUD_PIP_SPACING, 0);
+                    let style = match chamber {
+                        Chamber::Loaded => gfx::WHITE_FILL,
+                        Chamber::Shot | Chamber::Dud => gfx::WHITE_LINE,
+                        Chamber::Empty => continue,
+                    };
+                    if ammo_visible {
+                        Rectangle::new(pip, Size::new(3, 5))
+                            .into_styled(style)
+                            .draw(display)
+                            .unwrap();
+                    }
+                }
+                gun.capacity() as i32 * gfx::HUD_PIP_SPACING
+            } else {
+                let rounds = gfx::HUD_BAR_WIDTH * gun.ammo() as u32 / gun.capacity() as u32;
+                if ammo_visible {
+                    Rectangle::new(Point::new(ammo_x, 0), Size::new(gfx::HUD_BAR_WIDTH + 2, 5))
+                        .into_styled(gfx::WHITE_LINE)
+                        .draw(display)
+                        .unwrap();
+                    Rectangle::new(Point::new(ammo_x + 1, 1), Size::new(rounds, 3))
+                        .into_styled(gfx::WHITE_FILL)
+                        .draw(display)
+                        .unwrap();
+                }
+                gfx::HUD_BAR_WIDTH as i32 + 2
+            };
+            if game.holstered_gun().is_some() {
+                let plus = Point::new(ammo_x + ammo_width + 2, 0);
+                Text::with_baseline("+", plus, gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            // active power-ups, with a bar for the time that's left
+            for (i, (power_up, remaining)) in game.power_ups().active().enumerate() {
+                let x = gfx::HUD_POWER_UPS_X + i as i32 * gfx::HUD_POWER_UP_SPACING;
+                Text::with_baseline(
+                    power_up.label(),
+                    Point::new(x, 0),
+                    gfx::TEXT_STYLE,
+                    Baseline::Top,
+                )
+                .draw(display)
+                .unwrap();
+                let full = gfx::HUD_HEIGHT as u32 - 1;
+                let left = full * u8::min(remaining, power_up.duration()) as u32
+                    / power_up.duration() as u32;
+                let top = Point::new(x + 5, (full - left) as i32);
+                Rectangle::new(top, Size::new(1, left))
+                    .into_styled(gfx::WHITE_FILL)
+                    .draw(display)
                     .unwrap();
+            }
+
+            // score
+            let mut score = itoa::Buffer::new();
+            let score = score.format(game.score());
+            let score_x = gfx::text_align_right(score, gfx::SCREEN_WIDTH);
+            Text::with_baseline(
+                score,
+                Point::new(score_x, 0),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            // lives, left of the score
+            for i in 1..=game.lives() as i32 {
+                Circle::new(Point::new(score_x - 2 - i * gfx::LIFE_SPACING, 1), 3)
+                    .into_styled(gfx::WHITE_FILL)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            // time left or progress towards the target, below the rest of the HUD
+            if let Some((progress, full)) = game.mode_progress() {
+                let width = gfx::SCREEN_WIDTH as u32 * progress / full;
+                let y = gfx::HUD_HEIGHT as i32 - 1;
+                Rectangle::new(Point::new(0, y), Size::new(width, 1))
+                    .into_styled(gfx::WHITE_FILL)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            // combo multiplier
+            let multiplier = game.combo().multiplier();
+            if multiplier > 1 {
+                let position = Point::new(gfx::HUD_COMBO_X, 0);
+                draw_entry(display, "x", multiplier as u32, position).unwrap();
+            }
+
+            // freshly unlocked achievement
+            if let Some(achievement) = game.toast() {
+                let toast = Rectangle::new(Point::new(8, 52), Size::new(112, 11));
+                toast.into_styled(gfx::BLACK_FILL).draw(display).unwrap();
+                toast.into_styled(gfx::WHITE_LINE).draw(display).unwrap();
+                let name = achievement.name();
+                let x = (gfx::SCREEN_WIDTH as usize - name.len() * gfx::CHAR_WIDTH) as i32 / 2;
+                Text::with_baseline(name, Point::new(x, 55), gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+            }
+        }
+        Screen::Reload => {
+            // show ferris
+            if game.is_ferris_visible() {
+                let im = Image::new(&gfx::FERRIS_REVOLVER, Point::new(0, game.y() as i32));
+                im.draw(display).unwrap();
+            }
+
+            // show drum
+            Circle::new(Point::new(64, 2), 60)
+                .into_styled(gfx::WHITE_LINE)
+                .draw(display)
+                .unwrap();
 
-                // render chambers
-                for (&point, chamber) in gfx::CHAMBER_POSITIONS
-                    .iter()
-                    .zip(game.secondary_gun.chambers())
-                {
-                    match chamber {
-                        Chamber::Empty => {
-                            Circle::new(point, 16)
-                                .into_styled(gfx::WHITE_LINE)
-                                .draw(&mut display)
-                                .unwrap();
-                        }
-                        Chamber::Loaded => {
-                            Circle::new(point, 16)
-                                .into_styled(gfx::WHITE_FILL)
-                                .draw(&mut display)
-                                .unwrap();
-                            Circle::new(point + Point::new(5, 5), 6)
-                                .into_styled(gfx::BLACK_LINE)
-                                .draw(&mut display)
-                                .unwrap();
-                        }
-                        Chamber::Shot => {
-                            Circle::new(point, 16)
-                                .into_styled(gfx::WHITE_FILL)
-                                .draw(&mut display)
-                                .unwrap();
-                            Circle::new(point + Point::new(6, 6), 4)
-                                .into_styled(gfx::BLACK_FILL)
-                                .draw(&mut display)
-                                .unwrap();
-                        }
+            // render chambers
+            for (&point, chamber) in gfx::CHAMBER_POSITIONS
+                .iter()
+                .zip(game.secondary_gun.chambers())
+            {
+                match chamber {
+                    Chamber::Empty => {
+                        Circle::new(point, 16)
+                            .into_styled(gfx::WHITE_LINE)
+                            .draw(display)
+                            .unwrap();
+                    }
+                    Chamber::Loaded => {
+                        Circle::new(point, 16)
+                            .into_styled(gfx::WHITE_FILL)
+                            .draw(display)
+                            .unwrap();
+                        Circle::new(point + Point::new(5, 5), 6)
+                            .into_styled(gfx::BLACK_LINE)
+                            .draw(display)
+                            .unwrap();
+                    }
+                    Chamber::Shot => {
+                        Circle::new(point, 16)
+                            .into_styled(gfx::WHITE_FILL)
+                            .draw(display)
+                            .unwrap();
+                        Circle::new(point + Point::new(6, 6), 4)
+                            .into_styled(gfx::BLACK_FILL)
+                            .draw(display)
+                            .unwrap();
+                    }
+                    Chamber::Dud => {
+                        Circle::new(point, 16)
+                            .into_styled(gfx::WHITE_FILL)
+                            .draw(display)
+                            .unwrap();
+                        Line::new(point + Point::new(4, 4), point + Point::new(11, 11))
+                            .into_styled(gfx::BLACK_LINE)
+                            .draw(display)
+                            .unwrap();
+                        Line::new(point + Point::new(11, 4), point + Point::new(4, 11))
+                            .into_styled(gfx::BLACK_LINE)
+                            .draw(display)
+                            .unwrap();
                     }
                 }
+            }
+
+            // cartridges left in the reserve, in the middle of the drum
+            let mut reserve = itoa::Buffer::new();
+            let reserve = reserve.format(game.secondary_gun.reserve());
+            let x = gfx::DRUM_CENTER.x - (reserve.len() * gfx::CHAR_WIDTH) as i32 / 2;
+            Text::with_baseline(
+                reserve,
+                Point::new(x, gfx::DRUM_CENTER.y),
+                gfx::TEXT_STYLE,
+                Baseline::Middle,
+            )
+            .draw(display)
+            .unwrap();
+
+            // show indicator
+            Triangle::new(Point::new(62, 6), Point::new(57, 13), Point::new(66, 13))
+                .into_styled(gfx::WHITE_FILL)
+                .draw(display)
+                .unwrap();
 
-                // show indicator
-                Triangle::new(Point::new(62, 6), Point::new(57, 13), Point::new(66, 13))
+            // the lawn keeps moving while reloading, show it squeezed into the HUD strip
+            let scale = |distance: u8| {
+                let distance = u8::min(distance, opps::SPAWN_OFFSET_X) as i32;
+                distance * (gfx::MINI_LAWN_WIDTH - 3) / opps::SPAWN_OFFSET_X as i32 + 2
+            };
+            Rectangle::new(Point::zero(), Size::new(2, 5))
+                .into_styled(gfx::WHITE_FILL)
+                .draw(display)
+                .unwrap();
+            Line::new(Point::new(2, 4), Point::new(gfx::MINI_LAWN_WIDTH - 1, 4))
+                .into_styled(gfx::WHITE_LINE)
+                .draw(display)
+                .unwrap();
+            for opp in game.lawn.opponents() {
+                let x = scale(opp.distance());
+                Line::new(Point::new(x, 1), Point::new(x, 3))
+                    .into_styled(gfx::WHITE_LINE)
+                    .draw(display)
+                    .unwrap();
+            }
+            if let Some(boss) = game.lawn.boss() {
+                Rectangle::new(Point::new(scale(boss.distance()), 0), Size::new(3, 4))
                     .into_styled(gfx::WHITE_FILL)
-                    .draw(&mut display)
+                    .draw(display)
                     .unwrap();
             }
-            Screen::Wasted => {
+            let danger = game
+                .lawn
+                .closest()
+                .is_some_and(|distance| distance <= opps::DANGER_DISTANCE);
+            if danger && game.is_blink_on() {
+                let x = gfx::MINI_LAWN_WIDTH + 2;
+                Text::with_baseline("!", Point::new(x, 0), gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+            }
+
+            // show the hammer above the indicator while it's cocked
+            if game.secondary_gun.is_cocked() {
+                Rectangle::new(Point::new(59, 1), Size::new(5, 3))
+                    .into_styled(gfx::WHITE_FILL)
+                    .draw(display)
+                    .unwrap();
+            }
+        }
+        Screen::Wasted => match game.page() {
+            // score and where the points came from
+            0 => {
                 let im = Image::new(&gfx::WASTED, Point::new(0, 16));
-                im.draw(&mut display).unwrap();
+                im.draw(display).unwrap();
 
                 let mut score = itoa::Buffer::new();
                 let score = score.format(game.score());
@@ -300,53 +688,224 @@ fn main() -> ! {
                     gfx::TEXT_STYLE,
                     Baseline::Top,
                 )
-                .draw(&mut display)
+                .draw(display)
                 .unwrap();
-            }
-        }
-        display.flush().unwrap();
 
-        match (game.screen(), game.rumble) {
-            // always turn off rumble on these screens
-            (Screen::Start | Screen::Wasted, Some(_)) => {
-                rumble_off(&mut channel);
-                game.rumble = None;
+                // the mode, and the time it took if a sprint was finished
+                let mode = game.mode();
+                let x = gfx::text_align_center(mode.name(), gfx::SCREEN_WIDTH);
+                Text::with_baseline(
+                    mode.name(),
+                    Point::new(x, 2),
+                    gfx::TEXT_STYLE,
+                    Baseline::Top,
+                )
+                .draw(display)
+                .unwrap();
+                if let (GameMode::Sprint, Some(ticks)) = (mode, game.result()) {
+                    draw_entry(display, "TICKS ", ticks, Point::new(x, 9)).unwrap();
+                }
+                // which challenge it was and the code to check the result with
+                if let Some(code) = game.verification_code() {
+                    let period = &game.challenge().period().name()[..1];
+                    let date = game.challenge().date();
+                    let code = challenge::format_code(code);
+                    let parts = [
+                        (period, 26),
+                        (core::str::from_utf8(&date).unwrap(), 34),
+                        (core::str::from_utf8(&code).unwrap(), 78),
+                    ];
+                    for (text, x) in parts {
+                        Text::with_baseline(text, Point::new(x, 9), gfx::TEXT_STYLE, Baseline::Top)
+                            .draw(display)
+                            .unwrap();
+                    }
+                }
+
+                let breakdown = game.breakdown();
+                draw_entry(display, "BOSS ", breakdown.boss, Point::new(8, 2)).unwrap();
+                draw_entry(display, "KILLS ", breakdown.kills, Point::new(8, 51)).unwrap();
+                draw_entry(display, "COMBO ", breakdown.combo, Point::new(68, 51)).unwrap();
+                draw_entry(display, "RANGE ", breakdown.range, Point::new(8, 58)).unwrap();
+                draw_entry(display, "AIM ", breakdown.precision, Point::new(68, 58)).unwrap();
             }
-            // start rumble
-            (_, Some(Rumble::Start((duration, divider)))) => {
-                rumble_on(&mut channel, divider);
-                game.rumble = Some(Rumble::On(duration));
+            // how the run went
+            1 => {
+                let stats = game.stats();
+                draw_entry(display, "SHOTS ", stats.shots, Point::new(8, 2)).unwrap();
+                draw_entry(display, "HITS ", stats.hits, Point::new(68, 2)).unwrap();
+                draw_entry(display, "HIT% ", stats.accuracy(), Point::new(8, 14)).unwrap();
+                draw_entry(display, "DRY ", stats.dry_fires, Point::new(68, 14)).unwrap();
+                draw_entry(display, "KILLS ", stats.kills, Point::new(8, 26)).unwrap();
+                draw_entry(display, "RELOADS ", stats.reloads, Point::new(68, 26)).unwrap();
+                draw_entry(display, "BEST x", stats.max_combo as u32, Point::new(8, 38)).unwrap();
+                draw_entry(display, "TICKS ", stats.ticks, Point::new(8, 50)).unwrap();
             }
-            // stop rumble
-            (_, Some(Rumble::On(0))) => {
-                rumble_off(&mut channel);
-                game.rumble = None;
+            // kills per weapon
+            _ => {
+                Text::with_baseline("KILLS", Point::new(8, 2), gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+                for (i, kind) in guns::Kind::ALL.iter().enumerate() {
+                    let y = 14 + i as i32 * 12;
+                    Text::with_baseline(
+                        kind.name(),
+                        Point::new(8, y),
+                        gfx::TEXT_STYLE,
+                        Baseline::Top,
+                    )
+                    .draw(display)
+                    .unwrap();
+                    let kills = game.stats().kills_with(*kind);
+                    draw_entry(display, "", kills, Point::new(32, y)).unwrap();
+                }
             }
-            _ => (),
+        },
+        Screen::Challenge => {
+            let title = GameMode::Challenge.name();
+            let x = gfx::text_align_center(title, gfx::SCREEN_WIDTH);
+            Text::with_baseline(title, Point::new(x, 2), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
+
+            // the date and the period, the field that is being changed is underlined
+            let date = game.challenge().date();
+            let date = core::str::from_utf8(&date).unwrap();
+            let date_x = gfx::text_align_center(date, gfx::SCREEN_WIDTH);
+            Text::with_baseline(date, Point::new(date_x, 20), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
+            let period = game.challenge().period().name();
+            let period_x = gfx::text_align_center(period, gfx::SCREEN_WIDTH);
+            Text::with_baseline(
+                period,
+                Point::new(period_x, 32),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+            let (start, len, y) = match game.challenge_field().span() {
+                Some((start, len)) => (date_x + (start * gfx::CHAR_WIDTH) as i32, len, 27),
+                None => (period_x, period.len(), 39),
+            };
+            let end = start + (len * gfx::CHAR_WIDTH) as i32 - 2;
+            Line::new(Point::new(start, y), Point::new(end, y))
+                .into_styled(gfx::WHITE_LINE)
+                .draw(display)
+                .unwrap();
+
+            Text::with_baseline(
+                "Reload: next, hold: back",
+                Point::new(16, 47),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+            Text::with_baseline(
+                "Press shoot to start",
+                Point::new(25, 55),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
         }
+        Screen::Paused => {
+            Text::with_baseline("PAUSED", Point::new(52, 8), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
+            let mut score = itoa::Buffer::new();
+            let score = score.format(game.score());
+            let x = gfx::text_align_center(score, gfx::SCREEN_WIDTH);
+            Text::with_baseline(score, Point::new(x, 20), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
 
-        // test stuff
-        serial.write(&buf).ok();
-        /*
-        serial
-            .write(match last_action {
-                Some(Action::Rotate(Direction::Clockwise)) => b" rt cw",
-                Some(Action::Rotate(Direction::CounterClock)) => b" rt cc",
-                Some(Action::ReloadToggle) => b" rl",
-                Some(Action::Shoot) => b" shoot",
-                None => b" - none",
-            })
-            .ok();
-        */
-        serial.write(b"\n").ok();
+            // rotate to pick, shoot to confirm
+            for (i, item) in ["RESUME", "QUIT"].iter().enumerate() {
+                let y = 36 + i as i32 * 10;
+                Text::with_baseline(item, Point::new(52, y), gfx::TEXT_STYLE, Baseline::Top)
+                    .draw(display)
+                    .unwrap();
+                if (i == 1) == game.is_quit_selected() {
+                    Text::with_baseline(">", Point::new(44, y), gfx::TEXT_STYLE, Baseline::Top)
+                        .draw(display)
+                        .unwrap();
+                }
+            }
+        }
+        Screen::Records => match game.page() {
+            // counters across all games
+            0 => {
+                let lifetime = game.lifetime();
+                draw_entry(display, "GAMES ", lifetime.games, Point::new(8, 2)).unwrap();
+                draw_entry(display, "KILLS ", lifetime.kills, Point::new(8, 14)).unwrap();
+                draw_entry(display, "SHOTS ", lifetime.shots, Point::new(8, 26)).unwrap();
+                let accuracy = lifetime.best_accuracy;
+                draw_entry(display, "BEST HIT% ", accuracy, Point::new(8, 38)).unwrap();
+                let longest = lifetime.longest;
+                draw_entry(display, "LONGEST ", longest, Point::new(8, 50)).unwrap();
+            }
+            // the best result in every mode
+            1 => draw_bests(display, game.lifetime()),
+            // achievements, unlocked ones are marked
+            _ => {
+                for (i, achievement) in Achievement::ALL.iter().enumerate() {
+                    let y = 2 + i as i32 * 8;
+                    if game.lifetime().is_unlocked(*achievement) {
+                        Text::with_baseline("*", Point::new(8, y), gfx::TEXT_STYLE, Baseline::Top)
+                            .draw(display)
+                            .unwrap();
+                    }
+                    let name = Point::new(16, y);
+                    Text::with_baseline(achievement.name(), name, gfx::TEXT_STYLE, Baseline::Top)
+                        .draw(display)
+                        .unwrap();
+                }
+            }
+        },
+    }
 
-        if usb_dev.poll(&mut [&mut serial]) {
-            let mut buf = [0u8; 64];
-            serial.read(&mut buf[..]).ok();
+    // page indicator, scroll with the encoder
+    if game.pages() > 1 {
+        for page in 0..game.pages() {
+            let dot = Point::new(116 + page as i32 * 4, 2);
+            let size = if page == game.page() { 3 } else { 1 };
+            Rectangle::new(dot, Size::new(size, size))
+                .into_styled(gfx::WHITE_FILL)
+                .draw(display)
+                .unwrap();
         }
     }
 }
 
+/// The best result in every mode, sprints are timed
+fn draw_bests<D>(display: &mut D, lifetime: &Lifetime)
+where
+    D: DrawTarget<Color = BinaryColor>,
+    D::Error: Debug,
+{
+    for (i, mode) in GameMode::RANKED.iter().enumerate() {
+        let y = 2 + i as i32 * 12;
+        Text::with_baseline(
+            mode.name(),
+            Point::new(8, y),
+            gfx::TEXT_STYLE,
+            Baseline::Top,
+        )
+        .draw(display)
+        .unwrap();
+        let label = if *mode == GameMode::Sprint {
+            "TICKS "
+        } else {
+            ""
+        };
+        draw_entry(display, label, lifetime.best(*mode), Point::new(60, y)).unwrap();
+    }
+}
+
 enum Rotary {
     // 11 - default
     Rotary0,
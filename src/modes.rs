@@ -0,0 +1,99 @@
+use crate::guns::Revolver;
+use crate::pickups;
+
+// two minutes, ten ticks a second
+pub const TIME_ATTACK_TICKS: u32 = 120 * 10;
+pub const SPRINT_SCORE: u32 = 100;
+// the reserve never holds more than this in hardcore, so every miss hurts
+const HARDCORE_RESERVE: u8 = 12;
+
+/// The rules a game is played by, picked on the start screen
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum GameMode {
+    /// Play until wasted
+    #[default]
+    Endless,
+    /// Score as much as possible before the time runs out
+    TimeAttack,
+    /// No weapon or ammo drops and a small reserve
+    Hardcore,
+    /// Reach the target score as fast as possible
+    Sprint,
+    /// Time attack on a lawn that is the same for everyone on the same date
+    Challenge,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 5] = [
+        GameMode::Endless,
+        GameMode::TimeAttack,
+        GameMode::Hardcore,
+        GameMode::Sprint,
+        GameMode::Challenge,
+    ];
+    /// Modes with a best result, challenges are different every time
+    pub const RANKED: [GameMode; 4] = [
+        GameMode::Endless,
+        GameMode::TimeAttack,
+        GameMode::Hardcore,
+        GameMode::Sprint,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Endless => "ENDLESS",
+            GameMode::TimeAttack => "TIME ATTACK",
+            GameMode::Hardcore => "HARDCORE",
+            GameMode::Sprint => "SPRINT",
+            GameMode::Challenge => "CHALLENGE",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Ticks on the lawn until the game is over
+    pub fn time_limit(&self) -> Option<u32> {
+        match self {
+            GameMode::TimeAttack | GameMode::Challenge => Some(TIME_ATTACK_TICKS),
+            _ => None,
+        }
+    }
+
+    /// The score that ends the game
+    pub fn target_score(&self) -> Option<u32> {
+        match self {
+            GameMode::Sprint => Some(SPRINT_SCORE),
+            _ => None,
+        }
+    }
+
+    /// The most cartridges the revolver reserve can hold
+    pub fn reserve_limit(&self) -> u8 {
+        match self {
+            GameMode::Hardcore => HARDCORE_RESERVE,
+            _ => Revolver::MAX_RESERVE,
+        }
+    }
+
+    /// If killed opponents can drop this kind of pickup
+    pub fn drops(&self, kind: pickups::Kind) -> bool {
+        match self {
+            GameMode::Hardcore => kind != pickups::Kind::Ammo && kind.primary().is_none(),
+            _ => true,
+        }
+    }
+
+    /// Sprints are won on time, fewer ticks are better
+    pub fn is_better(&self, result: u32, best: u32) -> bool {
+        match self {
+            GameMode::Sprint => result < best,
+            _ => result > best,
+        }
+    }
+}
@@ -1,19 +1,116 @@
+use crate::boss::{self, Boss};
+use crate::director::Adjustment;
 use crate::gfx;
 use crate::guns;
+use crate::score;
 use core::iter::Flatten;
+use core::ops::RangeInclusive;
 use core::slice;
 use rand_core::RngCore;
 
 pub const SPAWN_OFFSET_X: u8 = gfx::SCREEN_WIDTH - gfx::FERRIS_OFFSET;
+pub const MIN_SPAWN_Y: u8 = gfx::HUD_HEIGHT;
 pub const MAX_SPAWN_Y: u8 = gfx::min(
     gfx::SCREEN_HEIGHT - gfx::OPPONENT_HEIGHT,
     gfx::FERRIS_MAX_Y + guns::MAX_GUARANTEED_REACH,
 );
 pub const HIT_PUSHBACK: u8 = 5;
+// opponents this close to ferris are shown as a danger on the reload screen
+pub const DANGER_DISTANCE: u8 = 16;
+
+// a rough sine wave, in pixels, one entry per step
+const WEAVE: [i8; 16] = [0, 2, 4, 5, 6, 5, 4, 2, 0, -2, -4, -5, -6, -5, -4, -2];
+const DODGE_INTERVAL: u8 = 4;
+const DODGE_STEP: i16 = 8;
+
+// opponents are clamped to the spawn area with `clamp_y`, so they always stay
+// reachable, but make sure there's enough room for them to actually move
+static_assertions::const_assert!((MAX_SPAWN_Y - MIN_SPAWN_Y) as i16 > 2 * DODGE_STEP);
+
+// gunners fire a projectile along their row every couple of ticks
+const GUNNER_FIRE_RATE: u8 = 40;
+// ticks per pixel a projectile travels
+const PROJECTILE_SPEED: u8 = 2;
+
+// the first formation wave marches in at this score, the following ones after
+// another `FORMATION_INTERVAL` points
+pub const FIRST_FORMATION_WAVE: u32 = 15;
+pub const FORMATION_INTERVAL: u32 = 20;
+// ticks of peace after a wave has been cleared
+const WAVE_BREAK: u8 = 30;
+const FORMATION_COLUMNS: u8 = 2;
+const FORMATION_MAX_ROWS: u8 = 3;
+const FORMATION_SPACING_X: u8 = 32;
+const FORMATION_SPACING_Y: u8 = 10;
+// how many pixels the formation advances towards ferris on every bounce
+const FORMATION_ADVANCE: u8 = 4;
+// ticks per step with the formation at full strength
+const FORMATION_SPEED: u32 = 4;
+
+// the formation needs to fit on the lawn and still have some room to march
+static_assertions::const_assert!((FORMATION_COLUMNS - 1) * FORMATION_SPACING_X < SPAWN_OFFSET_X);
+static_assertions::const_assert!(
+    (FORMATION_MAX_ROWS - 1) * FORMATION_SPACING_Y < MAX_SPAWN_Y - MIN_SPAWN_Y
+);
 
 pub struct Lawn {
     opponents: [Option<Opponent>; 25],
+    projectiles: [Option<Projectile>; 8],
     next_spawn: u8,
+    formation: Option<Formation>,
+    waves: u8,
+    next_wave: u32,
+    boss: Option<Boss>,
+    next_boss: u32,
+    adjustment: Adjustment,
+}
+
+#[derive(Clone, Copy)]
+enum Target {
+    Opponent(usize),
+    Boss,
+}
+
+/// Everything a shot hit and took out, across all of its rays
+#[derive(Default)]
+pub struct ShotResult {
+    /// Opponents that got hit, including the ones that died
+    pub hits: u32,
+    /// Opponents that died
+    pub kills: u32,
+    /// The health of the tiers the opponents that died came from, added up
+    pub toughness: u32,
+    /// Hits on boss parts, including the ones that got destroyed
+    pub boss_hits: u32,
+    /// Boss parts that got destroyed
+    pub boss_parts: u32,
+    /// Kills far away from ferris
+    pub long_range: u32,
+    /// Kills right through the middle of an opponent
+    pub precise: u32,
+    /// If the boss has been defeated
+    pub boss: bool,
+    /// Where the first opponent died, pickups fall out there
+    pub dropped_at: Option<(u8, u8)>,
+}
+
+impl ShotResult {
+    pub fn is_hit(&self) -> bool {
+        self.hits > 0 || self.boss_hits > 0
+    }
+
+    /// Merge the result of another ray into this one
+    pub fn add(&mut self, other: ShotResult) {
+        self.hits += other.hits;
+        self.kills += other.kills;
+        self.toughness += other.toughness;
+        self.boss_hits += other.boss_hits;
+        self.boss_parts += other.boss_parts;
+        self.long_range += other.long_range;
+        self.precise += other.precise;
+        self.boss |= other.boss;
+        self.dropped_at = self.dropped_at.or(other.dropped_at);
+    }
 }
 
 impl Default for Lawn {
@@ -23,89 +120,231 @@ impl Default for Lawn {
                 None, None, None, None, None, None, None, None, None, None, None, None, None, None,
                 None, None, None, None, None, None, None, None, None, None, None,
             ],
+            projectiles: [None, None, None, None, None, None, None, None],
             next_spawn: 20,
+            formation: None,
+            waves: 0,
+            next_wave: FIRST_FORMATION_WAVE,
+            boss: None,
+            next_boss: boss::BOSS_INTERVAL,
+            adjustment: Adjustment::default(),
         }
     }
 }
 
+#[derive(Clone, Copy)]
 struct Stats {
+    min_score: u32,
     spawn_rate: u8,
     cooldown: u8,
     concurrent: usize,
     speed: u32,
     health: u8,
+    movements: &'static [Movement],
+    // chance of an opponent being a gunner, out of 256
+    gunners: u8,
+}
+
+/// The difficulty goes up in tiers, every tier starts at its `min_score`
+static TIERS: [Stats; 5] = [
+    Stats {
+        min_score: 0,
+        spawn_rate: 10,
+        cooldown: 5,
+        concurrent: 1,
+        speed: 15,
+        health: 1,
+        movements: &[Movement::Straight],
+        gunners: 0,
+    },
+    Stats {
+        min_score: 3,
+        spawn_rate: 10,
+        cooldown: 5,
+        concurrent: 1,
+        speed: 10,
+        health: 1,
+        movements: &[Movement::Straight, Movement::Weave],
+        gunners: 0,
+    },
+    Stats {
+        min_score: 10,
+        spawn_rate: 10,
+        cooldown: 5,
+        concurrent: 1,
+        speed: 10,
+        health: 2,
+        movements: &[
+            Movement::Straight,
+            Movement::Weave,
+            Movement::Chase,
+            Movement::Dodge,
+        ],
+        gunners: 32,
+    },
+    Stats {
+        min_score: 35,
+        spawn_rate: 7,
+        cooldown: 5,
+        concurrent: 1,
+        speed: 5,
+        health: 2,
+        movements: &[
+            Movement::Weave,
+            Movement::Chase,
+            Movement::Dodge,
+            Movement::Evade,
+        ],
+        gunners: 64,
+    },
+    Stats {
+        min_score: 50,
+        spawn_rate: 5,
+        cooldown: 3,
+        concurrent: 1,
+        speed: 5,
+        health: 3,
+        movements: &[Movement::Weave, Movement::Dodge, Movement::Evade],
+        gunners: 96,
+    },
+];
+pub const TIER_COUNT: usize = TIERS.len();
+
+/// The difficulty tier at `score`, starting at 0
+pub fn tier(score: u32) -> usize {
+    TIERS
+        .iter()
+        .rposition(|stats| stats.min_score <= score)
+        .unwrap_or(0)
 }
 
 impl Stats {
     pub fn from_score(score: u32) -> &'static Stats {
-        if score < 3 {
-            &Stats {
-                spawn_rate: 10,
-                cooldown: 5,
-                concurrent: 1,
-                speed: 15,
-                health: 1,
-            }
-        } else if score < 10 {
-            &Stats {
-                spawn_rate: 10,
-                cooldown: 5,
-                concurrent: 1,
-                speed: 10,
-                health: 1,
-            }
-        } else if score < 35 {
-            &Stats {
-                spawn_rate: 10,
-                cooldown: 5,
-                concurrent: 1,
-                speed: 10,
-                health: 2,
-            }
-        } else if score < 50 {
-            &Stats {
-                spawn_rate: 7,
-                cooldown: 5,
-                concurrent: 1,
-                speed: 5,
-                health: 2,
-            }
-        } else {
-            &Stats {
-                spawn_rate: 5,
-                cooldown: 3,
-                concurrent: 1,
-                speed: 5,
-                health: 3,
-            }
+        &TIERS[tier(score)]
+    }
+
+    fn adjusted(&self, adjustment: &Adjustment) -> Stats {
+        // a percentage more or fewer ticks, but always at least one
+        let scale =
+            |ticks: u32, percent: i8| u32::max(1, ticks * (100 + percent as i32) as u32 / 100);
+        Stats {
+            spawn_rate: scale(self.spawn_rate as u32, adjustment.spawn_rate) as u8,
+            speed: scale(self.speed, adjustment.speed),
+            health: u8::max(1, self.health.saturating_add_signed(adjustment.health)),
+            gunners: self.gunners.saturating_add_signed(adjustment.gunners),
+            ..*self
+        }
+    }
+}
+
+/// What hit ferris
+#[derive(Clone, Copy, PartialEq)]
+pub enum Cause {
+    /// A gunner's projectile
+    Projectile,
+    /// An opponent that walked all the way up to ferris
+    Opponent,
+    /// A boss charge
+    Boss,
+}
+
+impl Cause {
+    pub const ALL: [Cause; 3] = [Cause::Projectile, Cause::Opponent, Cause::Boss];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Cause::Projectile => "projectile",
+            Cause::Opponent => "opponent",
+            Cause::Boss => "boss",
         }
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum Movement {
+    /// Walk straight towards ferris
+    Straight,
+    /// Weave up and down around the row the opponent spawned in
+    Weave,
+    /// Jump up and down every couple of steps
+    Dodge,
+    /// Drift towards the row ferris is aiming at
+    Chase,
+    /// Drift away from the row ferris is aiming at
+    Evade,
+    /// Member of a marching formation, positioned relative to the formation
+    Formation { dx: u8, dy: u8 },
+}
+
 pub struct Opponent {
     x: u8,
     y: u8,
+    origin_y: u8,
+    movement: Movement,
+    gunner: bool,
+    next_fire: u8,
+    steps: u8,
     speed: u32,
     next_step: u32,
     health: u8,
+    // the health of its tier before any adjustment, what killing it is worth
+    toughness: u8,
     cooldown: u8,
 }
 
 impl Opponent {
-    fn create<R: RngCore>(stats: &Stats, mut random: R) -> Self {
-        let mut y = [0u8];
-        random.fill_bytes(&mut y);
+    fn create<R: RngCore>(stats: &Stats, toughness: u8, mut random: R) -> Self {
+        let mut bytes = [0u8; 3];
+        random.fill_bytes(&mut bytes);
+        let y = MIN_SPAWN_Y + bytes[0] % (MAX_SPAWN_Y - MIN_SPAWN_Y);
+        let movement = stats.movements[bytes[1] as usize % stats.movements.len()];
+        Self {
+            x: SPAWN_OFFSET_X,
+            y,
+            origin_y: y,
+            movement,
+            gunner: bytes[2] < stats.gunners,
+            next_fire: GUNNER_FIRE_RATE,
+            steps: 0,
+            speed: stats.speed,
+            next_step: 0,
+            health: stats.health,
+            toughness,
+            cooldown: stats.cooldown,
+        }
+    }
+}
+
+impl Opponent {
+    fn formation_member(stats: &Stats, toughness: u8, dx: u8, dy: u8) -> Self {
         Self {
             x: SPAWN_OFFSET_X,
-            y: y[0] % MAX_SPAWN_Y,
+            y: MIN_SPAWN_Y,
+            origin_y: MIN_SPAWN_Y,
+            movement: Movement::Formation { dx, dy },
+            gunner: false,
+            next_fire: 0,
+            steps: 0,
             speed: stats.speed,
             next_step: 0,
             health: stats.health,
+            toughness,
             cooldown: stats.cooldown,
         }
     }
 }
 
+/// Keep a vertical position inside the area opponents are allowed to spawn in
+pub const fn clamp_y(y: i16) -> u8 {
+    if y < MIN_SPAWN_Y as i16 {
+        MIN_SPAWN_Y
+    } else if y > MAX_SPAWN_Y as i16 {
+        MAX_SPAWN_Y
+    } else {
+        y as u8
+    }
+}
+
 impl Opponent {
     pub fn x(&self) -> u8 {
         self.x + gfx::FERRIS_OFFSET
@@ -115,43 +354,300 @@ impl Opponent {
         self.y
     }
 
-    pub fn tick(&mut self) -> bool {
+    /// Pixels left until the opponent reaches ferris
+    pub fn distance(&self) -> u8 {
+        self.x
+    }
+
+    pub fn is_gunner(&self) -> bool {
+        self.gunner
+    }
+
+    fn fire(&mut self) -> Option<Projectile> {
+        if !self.gunner {
+            return None;
+        }
+        self.next_fire = self.next_fire.saturating_sub(1);
+        if self.next_fire > 0 {
+            return None;
+        }
+        self.next_fire = GUNNER_FIRE_RATE;
+        Some(Projectile {
+            x: self.x(),
+            y: self.y + gfx::OPPONENT_HEIGHT / 2,
+            next_step: PROJECTILE_SPEED,
+        })
+    }
+
+    /// Advance the opponent, `aim` is the row ferris is currently aiming at
+    pub fn tick(&mut self, aim: u8) -> bool {
+        // formation members are moved by their formation
+        if let Movement::Formation { .. } = self.movement {
+            return self.x == 0;
+        }
+
         self.next_step = self.next_step.saturating_sub(1);
         if self.next_step == 0 {
             self.x = self.x.saturating_sub(1);
+            self.step_y(aim);
             self.next_step = self.speed;
         }
 
         self.x == 0
     }
 
-    pub fn hit(&mut self, y: u8) -> bool {
-        if y >= self.y && y <= self.y + gfx::OPPONENT_HEIGHT {
-            self.health = self.health.saturating_sub(1);
-            self.x = u8::min(self.x.saturating_add(HIT_PUSHBACK), SPAWN_OFFSET_X);
-        }
+    fn step_y(&mut self, aim: u8) {
+        self.steps = self.steps.wrapping_add(1);
+        let y = self.y as i16;
+        // the row that would put the opponent centered in front of the barrel
+        let target = aim as i16 - (gfx::OPPONENT_HEIGHT / 2) as i16;
+        let y = match self.movement {
+            Movement::Straight | Movement::Formation { .. } => return,
+            Movement::Weave => {
+                self.origin_y as i16 + WEAVE[self.steps as usize % WEAVE.len()] as i16
+            }
+            Movement::Dodge => {
+                if !self.steps.is_multiple_of(DODGE_INTERVAL) {
+                    return;
+                }
+                if (self.steps / DODGE_INTERVAL).is_multiple_of(2) {
+                    y + DODGE_STEP
+                } else {
+                    y - DODGE_STEP
+                }
+            }
+            Movement::Chase => y + (target - y).signum(),
+            Movement::Evade => {
+                if y < target {
+                    y - 1
+                } else {
+                    y + 1
+                }
+            }
+        };
+        self.y = clamp_y(y);
+    }
+
+    pub fn in_row(&self, y: u8) -> bool {
+        y >= self.y && y <= self.y + gfx::OPPONENT_HEIGHT
+    }
+
+    /// Returns true if the opponent died
+    pub fn hit(&mut self) -> bool {
+        self.health = self.health.saturating_sub(1);
+        self.x = u8::min(self.x.saturating_add(HIT_PUSHBACK), SPAWN_OFFSET_X);
         self.health == 0
     }
 }
 
+/// A slow shot fired by an opponent, travelling along its row towards ferris
+pub struct Projectile {
+    x: u8,
+    y: u8,
+    next_step: u8,
+}
+
+impl Projectile {
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    /// Returns true if the projectile hit ferris
+    fn tick(&mut self, ferris_y: u8) -> bool {
+        self.next_step = self.next_step.saturating_sub(1);
+        if self.next_step == 0 {
+            self.x = self.x.saturating_sub(1);
+            self.next_step = PROJECTILE_SPEED;
+        }
+
+        self.x <= gfx::FERRIS_WIDTH && self.y >= ferris_y && self.y < ferris_y + gfx::FERRIS_HEIGHT
+    }
+}
+
+/// A grid of opponents that marches as a unit, Space Invaders style
+///
+/// The formation steps up and down, advances towards ferris every time it
+/// bounces off the edge of the lawn and speeds up as its members die.
+pub struct Formation {
+    x: u8,
+    y: u8,
+    max_y: u8,
+    descending: bool,
+    members: u8,
+    next_step: u32,
+}
+
+impl Formation {
+    fn spawn(stats: &Stats, toughness: u8, waves: u8, opponents: &mut [Option<Opponent>]) -> Self {
+        let rows = u8::min(2 + waves, FORMATION_MAX_ROWS);
+        let mut members = 0;
+        let mut slots = opponents.iter_mut().filter(|slot| slot.is_none());
+        for column in 0..FORMATION_COLUMNS {
+            for row in 0..rows {
+                let Some(slot) = slots.next() else {
+                    break;
+                };
+                let dx = column * FORMATION_SPACING_X;
+                let dy = row * FORMATION_SPACING_Y;
+                *slot = Some(Opponent::formation_member(stats, toughness, dx, dy));
+                members += 1;
+            }
+        }
+
+        let formation = Formation {
+            x: SPAWN_OFFSET_X - (FORMATION_COLUMNS - 1) * FORMATION_SPACING_X,
+            y: MIN_SPAWN_Y,
+            max_y: MAX_SPAWN_Y - (rows - 1) * FORMATION_SPACING_Y,
+            descending: true,
+            members,
+            next_step: FORMATION_SPEED,
+        };
+        formation.place(opponents);
+        formation
+    }
+
+    fn place(&self, opponents: &mut [Option<Opponent>]) {
+        for opp in opponents.iter_mut().flatten() {
+            if let Movement::Formation { dx, dy } = opp.movement {
+                opp.x = self.x + dx;
+                opp.y = self.y + dy;
+            }
+        }
+    }
+
+    fn step(&mut self, opponents: &mut [Option<Opponent>]) {
+        let edge = if self.descending {
+            self.y >= self.max_y
+        } else {
+            self.y <= MIN_SPAWN_Y
+        };
+
+        if edge {
+            self.descending = !self.descending;
+            if self.x > 0 {
+                self.x = self.x.saturating_sub(FORMATION_ADVANCE);
+            } else {
+                // the front column got through, the columns behind it keep advancing
+                for opp in opponents.iter_mut().flatten() {
+                    if let Movement::Formation { dx, .. } = &mut opp.movement {
+                        *dx = dx.saturating_sub(FORMATION_ADVANCE);
+                    }
+                }
+            }
+        } else if self.descending {
+            self.y += 1;
+        } else {
+            self.y -= 1;
+        }
+    }
+
+    /// Returns the number of members still alive
+    fn tick(&mut self, opponents: &mut [Option<Opponent>]) -> u8 {
+        self.members = opponents
+            .iter()
+            .flatten()
+            .filter(|opp| matches!(opp.movement, Movement::Formation { .. }))
+            .count() as u8;
+        if self.members == 0 {
+            return 0;
+        }
+
+        self.next_step = self.next_step.saturating_sub(1);
+        if self.next_step == 0 {
+            self.step(opponents);
+            // the formation speeds up as members die
+            self.next_step = u32::max(1, FORMATION_SPEED * self.members as u32 / 4);
+        }
+
+        // also undoes the pushback of hit members, the formation holds its shape
+        self.place(opponents);
+        self.members
+    }
+}
+
 impl Lawn {
-    pub fn tick<R: RngCore>(&mut self, score: u32, random: R) -> bool {
+    /// Advance everything on the lawn, returns what hit ferris, if anything
+    ///
+    /// Whatever hit ferris is removed from the lawn (or sent back, for bosses).
+    pub fn tick<R: RngCore>(&mut self, score: u32, ferris_y: u8, random: R) -> Option<Cause> {
+        let aim = ferris_y + guns::MAX_GUARANTEED_REACH;
+        let mut breached = None;
+
+        for slot in &mut self.projectiles {
+            if let Some(projectile) = slot {
+                if projectile.tick(ferris_y) {
+                    breached = Some(Cause::Projectile);
+                    *slot = None;
+                } else if projectile.x == 0 {
+                    *slot = None;
+                }
+            }
+        }
+
+        if let Some(boss) = &mut self.boss {
+            if boss.tick() {
+                breached = Some(Cause::Boss);
+                boss.knock_back();
+            }
+        }
+
+        if let Some(formation) = &mut self.formation {
+            if formation.tick(&mut self.opponents) == 0 {
+                // wave cleared, resume normal spawning after a short break
+                self.formation = None;
+                self.waves = self.waves.saturating_add(1);
+                self.next_wave = score.saturating_add(FORMATION_INTERVAL);
+                self.next_spawn = WAVE_BREAK;
+            }
+        }
+
         let mut count = 0;
-        for opp in self.opponents.iter_mut().flatten() {
-            if opp.tick() {
-                // game over
-                return true;
+        for slot in &mut self.opponents {
+            let Some(opp) = slot else {
+                continue;
+            };
+            if opp.tick(aim) {
+                breached = Some(Cause::Opponent);
+                *slot = None;
+                continue;
+            }
+            if let Some(projectile) = opp.fire() {
+                if let Some(slot) = self.projectiles.iter_mut().find(|slot| slot.is_none()) {
+                    *slot = Some(projectile);
+                }
             }
             count += 1;
         }
 
+        // no other spawns while a formation wave is marching or a boss is around
+        if self.formation.is_some() || self.boss.is_some() {
+            return breached;
+        }
+
         self.next_spawn = self.next_spawn.saturating_sub(1);
         if self.next_spawn == 0 {
-            let stats = Stats::from_score(score);
-            if count < stats.concurrent {
+            let tier = Stats::from_score(score);
+            let stats = &tier.adjusted(&self.adjustment);
+            if score >= self.next_boss {
+                // wait for the lawn to clear, then the boss enters
+                if count == 0 {
+                    self.boss = Some(Boss::new());
+                }
+            } else if score >= self.next_wave {
+                // wait for the lawn to clear, then the next wave marches in
+                if count == 0 {
+                    let formation =
+                        Formation::spawn(stats, tier.health, self.waves, &mut self.opponents);
+                    self.formation = Some(formation);
+                }
+            } else if count < stats.concurrent {
                 for slot in &mut self.opponents {
                     if slot.is_none() {
-                        let opponent = Opponent::create(stats, random);
+                        let opponent = Opponent::create(stats, tier.health, random);
                         *slot = Some(opponent);
                         break;
                     }
@@ -160,23 +656,260 @@ impl Lawn {
             self.next_spawn = stats.spawn_rate;
         }
 
-        false
+        breached
     }
 
-    pub fn shoot(&mut self, y: u8) -> bool {
-        for slot in &mut self.opponents {
+    /// Resolve a hitscan shot, every ray is the row of a pellet or bullet
+    ///
+    /// Each ray hits up to `penetration` targets in its row, closest to ferris first.
+    pub fn shoot(&mut self, rays: &[u8], penetration: u8) -> ShotResult {
+        let mut result = ShotResult::default();
+        for &y in rays {
+            self.shoot_ray(y, 0..=u8::MAX, penetration, &mut result);
+        }
+        result
+    }
+
+    /// Resolve a bullet that travelled through `x` in row `y` during the last tick
+    pub fn sweep(&mut self, y: u8, x: RangeInclusive<u8>, penetration: u8) -> ShotResult {
+        let mut result = ShotResult::default();
+        self.shoot_ray(y, x, penetration, &mut result);
+        result
+    }
+
+    fn shoot_ray(
+        &mut self,
+        y: u8,
+        x: RangeInclusive<u8>,
+        penetration: u8,
+        result: &mut ShotResult,
+    ) {
+        // only targets that overlap with `x` can be hit
+        let overlaps =
+            |left: u8, width: u8| left <= *x.end() && left.saturating_add(width) >= *x.start();
+
+        let mut targets = [(0u8, Target::Boss); 26];
+        let mut count = 0;
+        for (i, slot) in self.opponents.iter().enumerate() {
             if let Some(opp) = slot {
-                if opp.hit(y) {
-                    self.next_spawn += opp.cooldown;
-                    *slot = None;
-                    return true;
+                if opp.in_row(y) && overlaps(opp.x(), gfx::OPPONENT_WIDTH) {
+                    targets[count] = (opp.x(), Target::Opponent(i));
+                    count += 1;
+                }
+            }
+        }
+        if let Some(boss) = &self.boss {
+            if boss.in_row(y) && overlaps(boss.x(), boss::BOSS_WIDTH) {
+                targets[count] = (boss.x(), Target::Boss);
+                count += 1;
+            }
+        }
+
+        let targets = &mut targets[..count];
+        targets.sort_unstable_by_key(|(x, _)| *x);
+
+        for &(_, target) in targets.iter().take(penetration as usize) {
+            match target {
+                Target::Opponent(i) => {
+                    let slot = &mut self.opponents[i];
+                    let Some(opp) = slot else {
+                        continue;
+                    };
+                    result.hits += 1;
+                    let distance = opp.distance();
+                    let center = opp.y + gfx::OPPONENT_HEIGHT / 2;
+                    if opp.hit() {
+                        result.kills += 1;
+                        result.toughness += opp.toughness as u32;
+                        if distance >= score::LONG_RANGE_DISTANCE {
+                            result.long_range += 1;
+                        }
+                        if y.abs_diff(center) <= score::PRECISION_RANGE {
+                            result.precise += 1;
+                        }
+                        result.dropped_at = result.dropped_at.or(Some((opp.x(), center)));
+                        self.next_spawn += opp.cooldown;
+                        *slot = None;
+                    }
+                }
+                Target::Boss => {
+                    let Some(boss) = &mut self.boss else {
+                        continue;
+                    };
+                    result.boss_hits += 1;
+                    if boss.hit(y) {
+                        result.boss_parts += 1;
+                    }
+                    if boss.is_defeated() {
+                        // boss defeated, resume normal spawning after a short break
+                        result.boss = true;
+                        self.boss = None;
+                        self.next_boss = self.next_boss.saturating_add(boss::BOSS_INTERVAL);
+                        self.next_spawn = WAVE_BREAK;
+                    }
                 }
             }
         }
-        false
+    }
+
+    /// Take out every opponent and projectile at once, the boss is left alone
+    pub fn clear(&mut self) -> ShotResult {
+        let mut result = ShotResult::default();
+        for slot in &mut self.opponents {
+            if let Some(opp) = slot.take() {
+                result.hits += 1;
+                result.kills += 1;
+                result.toughness += opp.toughness as u32;
+            }
+        }
+        self.projectiles = Default::default();
+        if result.kills > 0 {
+            self.next_spawn = WAVE_BREAK;
+        }
+        result
     }
 
     pub fn opponents(&self) -> Flatten<slice::Iter<'_, Option<Opponent>>> {
         self.opponents.iter().flatten()
     }
+
+    pub fn projectiles(&self) -> Flatten<slice::Iter<'_, Option<Projectile>>> {
+        self.projectiles.iter().flatten()
+    }
+
+    pub fn formation(&self) -> Option<&Formation> {
+        self.formation.as_ref()
+    }
+
+    pub fn boss(&self) -> Option<&Boss> {
+        self.boss.as_ref()
+    }
+
+    /// Distance of whatever is closest to ferris, `None` if the lawn is empty
+    pub fn closest(&self) -> Option<u8> {
+        let boss = self.boss().map(|boss| boss.distance());
+        self.opponents().map(|opp| opp.distance()).chain(boss).min()
+    }
+
+    /// The number of formation waves that have been cleared
+    pub fn waves(&self) -> u8 {
+        self.waves
+    }
+
+    /// Opponents that spawn from now on differ from their tier by `adjustment`
+    pub fn set_adjustment(&mut self, adjustment: Adjustment) {
+        self.adjustment = adjustment;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game;
+
+    const MOVING: [Movement; 4] = [
+        Movement::Weave,
+        Movement::Dodge,
+        Movement::Chase,
+        Movement::Evade,
+    ];
+
+    fn opponent(movement: Movement, y: u8) -> Opponent {
+        Opponent {
+            x: SPAWN_OFFSET_X,
+            y,
+            origin_y: y,
+            movement,
+            gunner: false,
+            next_fire: 0,
+            steps: 0,
+            speed: 1,
+            next_step: 0,
+            health: 1,
+            toughness: 1,
+            cooldown: 0,
+        }
+    }
+
+    /// Every row the barrel of the revolver can be in, ferris starts at START_Y
+    fn aims() -> impl Iterator<Item = u8> {
+        let lowest =
+            game::START_Y - (game::START_Y - gfx::FERRIS_MIN_Y) / game::STEP_Y * game::STEP_Y;
+        (lowest..=gfx::FERRIS_MAX_Y)
+            .step_by(game::STEP_Y as usize)
+            .chain([gfx::FERRIS_MIN_Y])
+            .map(|y| y + guns::REVOLVER_OFFSET)
+    }
+
+    #[test]
+    fn clamp_y_keeps_to_the_spawn_area() {
+        assert_eq!(clamp_y(i16::MIN), MIN_SPAWN_Y);
+        assert_eq!(clamp_y(-1), MIN_SPAWN_Y);
+        assert_eq!(clamp_y(MIN_SPAWN_Y as i16 - 1), MIN_SPAWN_Y);
+        assert_eq!(clamp_y(MIN_SPAWN_Y as i16), MIN_SPAWN_Y);
+        assert_eq!(clamp_y(MIN_SPAWN_Y as i16 + 1), MIN_SPAWN_Y + 1);
+        assert_eq!(clamp_y(MAX_SPAWN_Y as i16 - 1), MAX_SPAWN_Y - 1);
+        assert_eq!(clamp_y(MAX_SPAWN_Y as i16), MAX_SPAWN_Y);
+        assert_eq!(clamp_y(MAX_SPAWN_Y as i16 + 1), MAX_SPAWN_Y);
+        assert_eq!(clamp_y(i16::MAX), MAX_SPAWN_Y);
+    }
+
+    #[test]
+    fn movement_stays_in_the_spawn_area() {
+        let edges = [MIN_SPAWN_Y, MAX_SPAWN_Y];
+        let aims = [0, MIN_SPAWN_Y, MAX_SPAWN_Y, u8::MAX - gfx::OPPONENT_HEIGHT];
+        for (i, movement) in MOVING.into_iter().enumerate() {
+            for y in edges {
+                for aim in aims {
+                    let mut opp = opponent(movement, y);
+                    // long enough to go through the weave and the dodges a couple of times
+                    for _ in 0..u8::MAX {
+                        opp.step_y(aim);
+                        assert!(
+                            (MIN_SPAWN_Y..=MAX_SPAWN_Y).contains(&opp.y),
+                            "MOVING[{i}] from {y} aiming at {aim} left the spawn area at {}",
+                            opp.y
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dodging_opponents_at_the_edges_keep_moving() {
+        for y in [MIN_SPAWN_Y, MAX_SPAWN_Y] {
+            let mut opp = opponent(Movement::Dodge, y);
+            let mut rows = [false; u8::MAX as usize + 1];
+            for _ in 0..4 * DODGE_INTERVAL {
+                opp.step_y(0);
+                rows[opp.y as usize] = true;
+            }
+            assert!(rows.iter().filter(|&&row| row).count() > 1);
+        }
+    }
+
+    #[test]
+    fn every_row_is_reachable() {
+        for y in MIN_SPAWN_Y..=MAX_SPAWN_Y {
+            let opp = opponent(Movement::Straight, y);
+            assert!(aims().any(|aim| opp.in_row(aim)), "row {y} is out of reach");
+        }
+    }
+
+    #[test]
+    fn moving_opponents_stay_reachable() {
+        for movement in MOVING {
+            for aim in aims() {
+                let mut opp = opponent(movement, MIN_SPAWN_Y + (MAX_SPAWN_Y - MIN_SPAWN_Y) / 2);
+                for _ in 0..SPAWN_OFFSET_X {
+                    let done = opp.tick(aim);
+                    assert!(aims().any(|aim| opp.in_row(aim)));
+                    if done {
+                        break;
+                    }
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,229 @@
+use crate::gfx;
+use crate::guns::Primary;
+use crate::modes::GameMode;
+use crate::powerups::PowerUp;
+use crate::rng::XorShift;
+use core::iter::Flatten;
+use core::slice;
+use rand_core::RngCore;
+
+// chance that a kill drops anything at all, out of 256
+const DROP_CHANCE: u32 = 48;
+// ticks until a pickup that wasn't collected disappears
+const PICKUP_TICKS: u8 = 80;
+// pickups blink this many ticks before they disappear
+const PICKUP_WARNING_TICKS: u8 = 20;
+// ticks per pixel a pickup drifts towards ferris
+const PICKUP_SPEED: u8 = 2;
+pub const PICKUP_SIZE: u8 = 8;
+
+/// Everything that can fall out of a killed opponent
+///
+/// `min_score` keeps the good stuff out of the early game, `weight` is relative
+/// to all other entries that are unlocked at the current score.
+pub const DROP_TABLE: &[Loot] = &[
+    Loot {
+        kind: Kind::Ammo,
+        weight: 40,
+        min_score: 0,
+    },
+    Loot {
+        kind: Kind::Life,
+        weight: 8,
+        min_score: 10,
+    },
+    Loot {
+        kind: Kind::Scorpio,
+        weight: 12,
+        min_score: 10,
+    },
+    Loot {
+        kind: Kind::Shotgun,
+        weight: 10,
+        min_score: 20,
+    },
+    Loot {
+        kind: Kind::Sniper,
+        weight: 8,
+        min_score: 40,
+    },
+    Loot {
+        kind: Kind::PowerUp(PowerUp::RapidFire),
+        weight: 8,
+        min_score: 15,
+    },
+    Loot {
+        kind: Kind::PowerUp(PowerUp::BulletTime),
+        weight: 6,
+        min_score: 25,
+    },
+    Loot {
+        kind: Kind::PowerUp(PowerUp::Freeze),
+        weight: 4,
+        min_score: 35,
+    },
+    Loot {
+        kind: Kind::PowerUp(PowerUp::ScreenClear),
+        weight: 2,
+        min_score: 50,
+    },
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Kind {
+    /// A handful of cartridges for the revolver
+    Ammo,
+    Life,
+    Scorpio,
+    Shotgun,
+    Sniper,
+    PowerUp(PowerUp),
+}
+
+impl Kind {
+    /// Single character shown inside the pickup
+    pub fn label(&self) -> &'static str {
+        match self {
+            Kind::Ammo => "A",
+            Kind::Life => "L",
+            Kind::Scorpio => "M",
+            Kind::Shotgun => "G",
+            Kind::Sniper => "S",
+            Kind::PowerUp(power_up) => power_up.label(),
+        }
+    }
+
+    /// The weapon this pickup contains, if any
+    pub fn primary(&self) -> Option<Primary> {
+        match self {
+            Kind::Scorpio => Some(Primary::scorpio()),
+            Kind::Shotgun => Some(Primary::shotgun()),
+            Kind::Sniper => Some(Primary::sniper()),
+            Kind::Ammo | Kind::Life | Kind::PowerUp(_) => None,
+        }
+    }
+}
+
+pub struct Loot {
+    pub kind: Kind,
+    pub weight: u8,
+    pub min_score: u32,
+}
+
+/// Something that fell out of an opponent, drifting towards ferris
+pub struct Pickup {
+    x: u8,
+    y: u8,
+    kind: Kind,
+    ttl: u8,
+    next_step: u8,
+}
+
+impl Pickup {
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    /// The row the pickup is in, it's centered on this
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Pickups blink shortly before they disappear
+    pub fn is_expiring(&self) -> bool {
+        self.ttl <= PICKUP_WARNING_TICKS
+    }
+
+    /// Returns true if ferris collected the pickup
+    ///
+    /// Being in its row is enough, however far away it still is.
+    fn tick(&mut self, ferris_y: u8) -> bool {
+        self.ttl = self.ttl.saturating_sub(1);
+        self.next_step = self.next_step.saturating_sub(1);
+        if self.next_step == 0 {
+            self.x = self.x.saturating_sub(1);
+            self.next_step = PICKUP_SPEED;
+        }
+
+        self.y >= ferris_y && self.y < ferris_y + gfx::FERRIS_HEIGHT
+    }
+}
+
+pub struct Pickups {
+    pickups: [Option<Pickup>; 4],
+    random: XorShift,
+    mode: GameMode,
+}
+
+impl Pickups {
+    /// Drops are rolled from `seed`, so the same game gets the same pickups
+    pub fn new(seed: u32, mode: GameMode) -> Self {
+        Self {
+            pickups: [None, None, None, None],
+            random: XorShift::new(seed),
+            mode,
+        }
+    }
+
+    /// Roll the drop table for an opponent that died at `x`/`y`
+    pub fn roll(&mut self, x: u8, y: u8, score: u32) {
+        if self.random.next_u32() % 256 >= DROP_CHANCE {
+            return;
+        }
+
+        let mode = self.mode;
+        let unlocked = || {
+            DROP_TABLE
+                .iter()
+                .filter(|loot| loot.min_score <= score && mode.drops(loot.kind))
+        };
+        let total: u32 = unlocked().map(|loot| loot.weight as u32).sum();
+        if total == 0 {
+            return;
+        }
+        let mut roll = self.random.next_u32() % total;
+        let Some(loot) = unlocked().find(|loot| {
+            let found = roll < loot.weight as u32;
+            roll = roll.saturating_sub(loot.weight as u32);
+            found
+        }) else {
+            return;
+        };
+
+        // if the lawn is already full of pickups, this one is lost
+        if let Some(slot) = self.pickups.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(Pickup {
+                x,
+                y,
+                kind: loot.kind,
+                ttl: PICKUP_TICKS,
+                next_step: PICKUP_SPEED,
+            });
+        }
+    }
+
+    /// Advance all pickups, returns the ones ferris collected during this tick
+    pub fn tick(&mut self, ferris_y: u8) -> [Option<Kind>; 4] {
+        let mut collected = [None; 4];
+        for (slot, collected) in self.pickups.iter_mut().zip(&mut collected) {
+            let Some(pickup) = slot else {
+                continue;
+            };
+            if pickup.tick(ferris_y) {
+                *collected = Some(pickup.kind);
+                *slot = None;
+            } else if pickup.ttl == 0 || pickup.x == 0 {
+                *slot = None;
+            }
+        }
+        collected
+    }
+
+    pub fn iter(&self) -> Flatten<slice::Iter<'_, Option<Pickup>>> {
+        self.pickups.iter().flatten()
+    }
+}
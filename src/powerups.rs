@@ -0,0 +1,89 @@
+// ticks between shots with rapid fire, for guns that don't fire automatically
+pub const RAPID_FIRE_RATE: u8 = 3;
+// rapid fire stacks, but not forever
+const MAX_RAPID_FIRE_TICKS: u8 = 200;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PowerUp {
+    /// Every gun fires automatically, automatic guns twice as fast
+    RapidFire,
+    /// Nothing on the lawn moves
+    Freeze,
+    /// Everything on the lawn moves at half speed
+    BulletTime,
+    /// Takes out every opponent on the lawn at once, the boss shrugs it off
+    ScreenClear,
+}
+
+// power-ups that last for a while, in the order they're shown in the HUD
+const TIMED: [PowerUp; 3] = [PowerUp::RapidFire, PowerUp::Freeze, PowerUp::BulletTime];
+
+impl PowerUp {
+    /// Ticks the power-up lasts when picked up, 0 for instant ones
+    pub fn duration(&self) -> u8 {
+        match self {
+            PowerUp::RapidFire => 100,
+            PowerUp::Freeze => 40,
+            PowerUp::BulletTime => 80,
+            PowerUp::ScreenClear => 0,
+        }
+    }
+
+    /// Single character for pickups and the HUD
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerUp::RapidFire => "R",
+            PowerUp::Freeze => "F",
+            PowerUp::BulletTime => "T",
+            PowerUp::ScreenClear => "X",
+        }
+    }
+
+    fn timer(&self) -> Option<usize> {
+        TIMED.iter().position(|timed| timed == self)
+    }
+}
+
+/// Remaining ticks of every timed power-up
+///
+/// Different power-ups are active at the same time, freeze wins over bullet time.
+/// Picking up rapid fire again adds to its timer, the others are refreshed.
+#[derive(Default)]
+pub struct PowerUps {
+    timers: [u8; TIMED.len()],
+}
+
+impl PowerUps {
+    pub fn activate(&mut self, power_up: PowerUp) {
+        let Some(i) = power_up.timer() else {
+            return;
+        };
+        let timer = &mut self.timers[i];
+        *timer = match power_up {
+            PowerUp::RapidFire => u8::min(
+                timer.saturating_add(power_up.duration()),
+                MAX_RAPID_FIRE_TICKS,
+            ),
+            _ => u8::max(*timer, power_up.duration()),
+        };
+    }
+
+    pub fn is_active(&self, power_up: PowerUp) -> bool {
+        power_up.timer().is_some_and(|i| self.timers[i] > 0)
+    }
+
+    pub fn tick(&mut self) {
+        for timer in &mut self.timers {
+            *timer = timer.saturating_sub(1);
+        }
+    }
+
+    /// Every active power-up with its remaining ticks
+    pub fn active(&self) -> impl Iterator<Item = (PowerUp, u8)> + '_ {
+        TIMED
+            .iter()
+            .zip(&self.timers)
+            .filter(|(_, &timer)| timer > 0)
+            .map(|(&power_up, &timer)| (power_up, timer))
+    }
+}
@@ -0,0 +1,62 @@
+use crate::opps;
+
+// hits in a row it takes to raise the multiplier by one
+const COMBO_STEP: u8 = 4;
+pub const MAX_MULTIPLIER: u8 = 4;
+// ticks without a hit until the multiplier drops by one
+const COMBO_TICKS: u8 = 30;
+
+// kills this far away from ferris are worth a bonus
+pub const LONG_RANGE_DISTANCE: u8 = opps::SPAWN_OFFSET_X - 16;
+pub const LONG_RANGE_BONUS: u32 = 1;
+// kills this close to the middle of an opponent are worth a bonus
+pub const PRECISION_RANGE: u8 = 2;
+pub const PRECISION_BONUS: u32 = 1;
+
+/// Hits in a row raise the multiplier, a miss or dry fire resets it
+#[derive(Default)]
+pub struct Combo {
+    streak: u8,
+    timer: u8,
+}
+
+impl Combo {
+    pub fn multiplier(&self) -> u8 {
+        u8::min(1 + self.streak / COMBO_STEP, MAX_MULTIPLIER)
+    }
+
+    pub fn hit(&mut self, hits: u32) {
+        let max_streak = (MAX_MULTIPLIER - 1) * COMBO_STEP;
+        self.streak = u8::min(self.streak.saturating_add(hits as u8), max_streak);
+        self.timer = COMBO_TICKS;
+    }
+
+    pub fn miss(&mut self) {
+        self.streak = 0;
+        self.timer = 0;
+    }
+
+    /// Without hits the multiplier slowly drops back, one level at a time
+    pub fn tick(&mut self) {
+        if self.streak == 0 {
+            return;
+        }
+        self.timer = self.timer.saturating_sub(1);
+        if self.timer == 0 {
+            self.streak = (self.multiplier() - 1).saturating_sub(1) * COMBO_STEP;
+            self.timer = COMBO_TICKS;
+        }
+    }
+}
+
+/// Where the points of a game came from, adds up to the score
+#[derive(Default, Clone, Copy)]
+pub struct Breakdown {
+    /// Opponents and boss parts, a point each
+    pub kills: u32,
+    /// Everything the multiplier added on top
+    pub combo: u32,
+    pub range: u32,
+    pub precision: u32,
+    pub boss: u32,
+}
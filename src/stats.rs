@@ -0,0 +1,65 @@
+use crate::guns;
+use crate::opps::{self, Cause};
+
+/// Everything worth knowing about a single game, from start to wasted
+#[derive(Default, Clone, Copy)]
+pub struct RunStats {
+    /// Shots that were actually fired, a shotgun shell counts as a single shot
+    pub shots: u32,
+    /// Shots that hit at least one opponent or boss part
+    pub hits: u32,
+    pub kills: u32,
+    /// Trigger pulls that didn't fire a round, misfires included
+    pub dry_fires: u32,
+    /// How often ferris got hurt
+    pub damage: u32,
+    /// How often the reload screen was opened
+    pub reloads: u32,
+    /// Ticks spent on the lawn
+    pub ticks: u32,
+    /// The highest combo multiplier that was reached
+    pub max_combo: u8,
+    /// What took the last life, `None` if the game ended otherwise
+    pub death: Option<Cause>,
+    weapon_kills: [u32; guns::Kind::ALL.len()],
+    damage_causes: [u32; Cause::ALL.len()],
+    tier_ticks: [u32; opps::TIER_COUNT],
+}
+
+impl RunStats {
+    /// Shots that hit something, in percent
+    pub fn accuracy(&self) -> u32 {
+        if self.shots == 0 {
+            return 0;
+        }
+        self.hits * 100 / self.shots
+    }
+
+    pub fn add_kills(&mut self, kind: guns::Kind, kills: u32) {
+        self.weapon_kills[kind as usize] += kills;
+    }
+
+    pub fn kills_with(&self, kind: guns::Kind) -> u32 {
+        self.weapon_kills[kind as usize]
+    }
+
+    pub fn add_damage(&mut self, cause: Cause) {
+        self.damage += 1;
+        self.damage_causes[cause as usize] += 1;
+    }
+
+    pub fn damage_by(&self, cause: Cause) -> u32 {
+        self.damage_causes[cause as usize]
+    }
+
+    /// Count a tick on the lawn, at the difficulty tier of `score`
+    pub fn add_tick(&mut self, score: u32) {
+        self.ticks += 1;
+        self.tier_ticks[opps::tier(score)] += 1;
+    }
+
+    /// Ticks spent at a difficulty tier
+    pub fn ticks_in(&self, tier: usize) -> u32 {
+        self.tier_ticks[tier]
+    }
+}